@@ -0,0 +1,66 @@
+/// Generates the registry of solutions that the runner binary executes in-process.
+///
+/// Every `src/bin/YYYY-NN.rs` is included as a module of the runner. Solutions that declare crate-level
+/// attributes (e.g. `#![feature(...)]`) can not be compiled as modules and are left to `cargo run`.
+/// Lints are silenced for the included modules, they are already reported for each solution binary.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<(u16, u8, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| entries.filter_map(Result::ok).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let stem = path.file_stem()?.to_str()?;
+            let (year, day) = stem.split_once('-')?;
+
+            if path.extension()? != "rs" || year.len() != 4 || day.len() != 2 {
+                return None;
+            }
+
+            let year = year.parse::<u16>().ok()?;
+            let day = day
+                .parse::<u8>()
+                .ok()
+                .filter(|day| (1..=25).contains(day))?;
+
+            let source = fs::read_to_string(&path).ok()?;
+            if source
+                .lines()
+                .any(|line| line.trim_start().starts_with("#!["))
+            {
+                return None;
+            }
+
+            Some((year, day, path.to_str()?.to_string()))
+        })
+        .collect();
+
+    days.sort_unstable();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    // NOTE: `solution!` derives the year of a module from its name.
+    for (year, day, path) in &days {
+        modules.push_str(&format!(
+            "#[cfg(not(test))]\n#[path = {path:?}]\n#[allow(warnings, clippy::all)]\nmod day_{year}_{day:02};\n"
+        ));
+        entries.push_str(&format!(
+            "    #[cfg(not(test))]\n    &day_{year}_{day:02}::SOLUTION,\n"
+        ));
+    }
+
+    let registry = format!(
+        "{modules}\n/// Solutions compiled into this binary.\npub static SOLUTIONS: &[&advent_of_code::template::registry::Solution] = &[\n{entries}];\n"
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, registry).unwrap();
+}
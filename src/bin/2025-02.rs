@@ -114,28 +114,28 @@ fn sum_repeating_patterns(range: (u64, u64)) -> Option<u64> {
     Some(found_numbers.iter().sum())
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
     let mut answer = 0u64;
 
     for str_range in input.split(',') {
-        let range = get_range(str_range).ok()?;
+        let range = get_range(str_range)?;
         if let Some(partial) = sum_mirror_numbers(range) {
             answer += partial;
         }
     }
-    Some(answer)
+    Ok(answer)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
     let mut answer = 0u64;
 
     for str_range in input.split(',') {
-        let range = get_range(str_range).ok()?;
+        let range = get_range(str_range)?;
         if let Some(partial) = sum_repeating_patterns(range) {
             answer += partial;
         }
     }
-    Some(answer)
+    Ok(answer)
 }
 
 #[cfg(test)]
@@ -145,13 +145,13 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1227775554));
+        assert_eq!(result.ok(), Some(1227775554));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4174379265));
+        assert_eq!(result.ok(), Some(4174379265));
     }
 }
 
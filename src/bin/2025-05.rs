@@ -1,4 +1,6 @@
-advent_of_code::solution!(5);
+advent_of_code::solver!(5, Day05);
+
+use advent_of_code::template::solver::Solver;
 
 fn parse(input: &str) -> (Vec<(u64, u64)>, Vec<u64>) {
     // 1) découper en deux blocs autour de la ligne vide
@@ -55,20 +57,30 @@ fn is_fresh(id: &u64, ranges: &Vec<(u64, u64)>) -> bool {
         .any(|(start, end)| *start <= *id && *id <= *end)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (ranges, ids) = parse(input);
-    let merged_ranges = merge_intervals(ranges);
-    Some(ids.iter().filter(|id| is_fresh(id, &merged_ranges)).count() as u64)
-}
+struct Day05;
+
+impl Solver for Day05 {
+    // intervalles fusionnés et IDs
+    type Input = (Vec<(u64, u64)>, Vec<u64>);
+    type Output1 = Option<u64>;
+    type Output2 = Option<u64>;
+
+    fn parse(input: &str) -> Self::Input {
+        let (ranges, ids) = parse(input);
+        (merge_intervals(ranges), ids)
+    }
+
+    fn part_one((merged_ranges, ids): &Self::Input) -> Option<u64> {
+        Some(ids.iter().filter(|id| is_fresh(id, merged_ranges)).count() as u64)
+    }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (ranges, _ids) = parse(input);
-    let merged_ranges = merge_intervals(ranges);
-    Some(
-        merged_ranges
-            .iter()
-            .fold(0, |accumul, (left, right)| accumul + (right - left + 1)),
-    )
+    fn part_two((merged_ranges, _ids): &Self::Input) -> Option<u64> {
+        Some(
+            merged_ranges
+                .iter()
+                .fold(0, |accumul, (left, right)| accumul + (right - left + 1)),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -77,13 +89,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day05::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day05::part_one(&input);
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day05::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day05::part_two(&input);
         assert_eq!(result, Some(14));
     }
 }
@@ -1,81 +1,268 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, time, verify};
 use args::{AppArguments, parse};
+use solutions::SOLUTIONS;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::{calendar::SystemClock, commands::today};
+
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::calendar::check_day;
+    use advent_of_code::template::regression;
+    use advent_of_code::template::report::ReportOptions;
+    use advent_of_code::template::runner::{BenchOptions, InputSource, Timeouts};
+    use advent_of_code::template::{Day, Year, parse_days, parse_years};
+    use std::collections::HashSet;
+    use std::error::Error;
+    use std::ffi::OsString;
     use std::process;
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
             day: Day,
         },
         Scaffold {
+            year: Year,
             day: Day,
             download: bool,
             overwrite: bool,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            force: bool,
+            record: bool,
+            watch: bool,
+            test: bool,
+            input: InputSource,
         },
         All {
+            years: Vec<Year>,
             release: bool,
+            timeouts: Timeouts,
+            jobs: usize,
+            resources: bool,
+            report: Option<ReportOptions>,
         },
         Time {
+            years: Vec<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            bench: BenchOptions,
+            check: bool,
+            threshold: f64,
+            history: Option<Day>,
+            timeouts: Timeouts,
+            memory: bool,
+            resources: bool,
+            report: Option<ReportOptions>,
+        },
+        Verify {
+            year: Year,
+            days: Option<HashSet<Day>>,
+            release: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+    }
+
+    /// Commands that work on a single year reject `--year` lists.
+    fn single_year(years: &[Year]) -> Result<Year, Box<dyn Error>> {
+        match years {
+            [year] => Ok(*year),
+            _ => Err("this command takes a single year.".into()),
+        }
+    }
+
+    /// `--example` may be passed without a number, which selects the first example.
+    fn default_example_number(args: impl Iterator<Item = OsString>) -> Vec<OsString> {
+        let mut result = vec![];
+        let mut args = args.peekable();
+
+        while let Some(arg) = args.next() {
+            let is_bare_example = arg == "--example"
+                && args
+                    .peek()
+                    .is_none_or(|next| next.to_str().is_none_or(|n| n.parse::<u8>().is_err()));
+
+            result.push(arg);
+
+            if is_bare_example {
+                result.push("1".into());
+            }
+        }
+
+        result
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut args =
+            pico_args::Arguments::from_vec(default_example_number(std::env::args_os().skip(1)));
+        let subcommand = args.subcommand()?;
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+        let years = match args.opt_value_from_fn("--year", parse_years)? {
+            Some(years) => years,
+            None => vec![Year::from_env().ok_or("no year given. Pass `--year` or set AOC_YEAR.")?],
+        };
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+
+                if jobs == 0 {
+                    return Err("`--jobs` must be at least 1.".into());
+                }
+
+                AppArguments::All {
+                    years,
+                    release: args.contains("--release"),
+                    timeouts: Timeouts::from_args(&mut args)?,
+                    jobs,
+                    resources: args.contains("--resources"),
+                    report: ReportOptions::from_args(&mut args)?,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let bench = BenchOptions::from_args(&mut args)?;
+                let check = args.contains("--check");
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(regression::DEFAULT_THRESHOLD);
+                let history: Option<Day> = args.opt_value_from_str("--history")?;
+                let timeouts = Timeouts::from_args(&mut args)?;
+                let memory = args.contains("--memory");
+                let resources = args.contains("--resources");
+                let report = ReportOptions::from_args(&mut args)?;
+
+                if let Some(day) = history {
+                    check_day(single_year(&years)?, day)?;
+                }
+
+                let day: Option<Day> = args.opt_free_from_str()?;
+
+                if let Some(day) = day {
+                    for year in &years {
+                        check_day(*year, day)?;
+                    }
+                }
 
                 AppArguments::Time {
+                    years,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day,
                     store,
+                    bench,
+                    check,
+                    threshold,
+                    history,
+                    timeouts,
+                    memory,
+                    resources,
+                    report,
+                }
+            }
+            Some("download") => {
+                let year = single_year(&years)?;
+                AppArguments::Download {
+                    year,
+                    day: check_day(year, args.free_from_str()?)?,
+                }
+            }
+            Some("read") => {
+                let year = single_year(&years)?;
+                AppArguments::Read {
+                    year,
+                    day: check_day(year, args.free_from_str()?)?,
+                }
+            }
+            Some("scaffold") => {
+                let year = single_year(&years)?;
+                AppArguments::Scaffold {
+                    year,
+                    day: check_day(year, args.free_from_str()?)?,
+                    download: args.contains("--download"),
+                    overwrite: args.contains("--overwrite"),
+                }
+            }
+            Some("solve") => {
+                let year = single_year(&years)?;
+                let submit = args.opt_value_from_str("--submit")?;
+                let record = args.contains("--record");
+                let input = InputSource::from_args(&mut args)?;
+
+                if (submit.is_some() || record) && input != InputSource::Inputs {
+                    return Err("`--submit` and `--record` only work with the puzzle input.".into());
+                }
+
+                let force = args.contains("--force");
+                let dhat = args.contains("--dhat");
+                let watch = args.contains("--watch");
+
+                if watch && input == InputSource::Stdin {
+                    return Err("`--watch` can not read the input from stdin.".into());
+                }
+
+                if watch {
+                    let flags = [
+                        ("--submit", submit.is_some()),
+                        ("--force", force),
+                        ("--record", record),
+                        ("--dhat", dhat),
+                    ];
+
+                    if let Some((flag, _)) = flags.iter().find(|(_, set)| *set) {
+                        return Err(format!("`--watch` can not be combined with `{flag}`.").into());
+                    }
+                }
+
+                AppArguments::Solve {
+                    year,
+                    day: check_day(year, args.free_from_str()?)?,
+                    release: args.contains("--release"),
+                    submit,
+                    force,
+                    dhat,
+                    record,
+                    watch,
+                    test: args.contains("--test"),
+                    input,
+                }
+            }
+            Some("verify") => {
+                let year = single_year(&years)?;
+                let release = args.contains("--release");
+                let days = args.opt_free_from_fn(parse_days)?;
+
+                for day in days.iter().flatten() {
+                    check_day(year, *day)?;
+                }
+
+                AppArguments::Verify {
+                    year,
+                    release,
+                    days,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -102,43 +289,90 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                years,
+                release,
+                timeouts,
+                jobs,
+                resources,
+                report,
+            } => all::handle(
+                SOLUTIONS,
+                &years,
+                release,
+                timeouts,
+                jobs,
+                resources,
+                report.as_ref(),
+            ),
+            AppArguments::Time {
+                years,
+                day,
+                all,
+                store,
+                bench,
+                check,
+                threshold,
+                history,
+                timeouts,
+                memory,
+                resources,
+                report,
+            } => match history {
+                Some(day) => time::handle_history(years[0], day),
+                None => time::handle(
+                    SOLUTIONS,
+                    &years,
+                    day,
+                    all,
+                    store,
+                    bench,
+                    check,
+                    threshold,
+                    timeouts,
+                    memory,
+                    resources,
+                    report.as_ref(),
+                ),
+            },
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
             AppArguments::Scaffold {
+                year,
                 day,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(year, day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(year, day);
                 }
             }
             AppArguments::Solve {
+                year,
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+                force,
+                record,
+                watch,
+                test,
+                input,
+            } => {
+                if watch {
+                    solve::watch(year, day, release, test, &input);
+                } else {
+                    solve::handle(year, day, release, dhat, submit, force, record, &input);
+                }
             }
+            AppArguments::Verify {
+                year,
+                days,
+                release,
+            } => verify::handle(SOLUTIONS, year, days, release),
+            #[cfg(feature = "today")]
+            AppArguments::Today { wait } => today::handle(&SystemClock, wait),
         },
     };
 }
@@ -0,0 +1,119 @@
+//! A global allocator that counts allocations, enabled with the `count-allocs` feature.
+//!
+//! Unlike dhat, it only adds a few atomic operations to every allocation, so runtimes of release
+//! builds stay representative. Every solution binary (and the runner binary) links the library,
+//! so the allocator is in place for all parts that `solution!` and `solver!` run.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::template::stats::MemoryStats;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and counts allocations, allocated bytes and peak live bytes.
+pub struct CountingAlloc;
+
+fn record_alloc(size: usize) {
+    let size = size as u64;
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    TOTAL_BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    LIVE_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+}
+
+// SAFETY: all calls are forwarded to the system allocator, counting has no effect on the memory.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage between [`Counter::start`] and [`Counter::stop`].
+/// Allocations of all threads are counted.
+pub struct Counter {
+    allocations: u64,
+    total_bytes: u64,
+    live_bytes: u64,
+}
+
+impl Counter {
+    /// Start counting. Bytes that are already allocated do not count towards the peak.
+    pub fn start() -> Self {
+        let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+        PEAK_BYTES.store(live_bytes, Ordering::Relaxed);
+
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            total_bytes: TOTAL_BYTES.load(Ordering::Relaxed),
+            live_bytes,
+        }
+    }
+
+    pub fn stop(&self) -> MemoryStats {
+        MemoryStats {
+            total_bytes: TOTAL_BYTES.load(Ordering::Relaxed) - self.total_bytes,
+            peak_bytes: PEAK_BYTES
+                .load(Ordering::Relaxed)
+                .saturating_sub(self.live_bytes),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Counter;
+    use std::hint::black_box;
+
+    #[test]
+    fn counts_allocations() {
+        let counter = Counter::start();
+
+        let mut values: Vec<u64> = Vec::with_capacity(128);
+        values.extend(0..128);
+        black_box(&values);
+        drop(values);
+
+        let memory = counter.stop();
+        // other tests allocate concurrently, so the counts are lower bounds.
+        assert!(memory.allocations >= 1);
+        assert!(memory.total_bytes >= 1024);
+        assert!(memory.peak_bytes >= 1024);
+    }
+}
@@ -0,0 +1,240 @@
+/// Store of known correct answers, used to detect solutions whose output changed.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year, data_dir};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the known answers of a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Result of comparing an answer with the known one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnswerCheck {
+    Correct,
+    Wrong,
+    Unknown,
+}
+
+/// Represents the known answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(data_dir(year).join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns no answers.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_dir(year).join(ANSWERS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record the answer of a part, replacing a previously known answer.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Compare an answer with the known answer of a part.
+    pub fn verify(&self, day: Day, part: u8, value: &str) -> AnswerCheck {
+        match self.get(day, part) {
+            Some(known) if known == value => AnswerCheck::Correct,
+            Some(_) => AnswerCheck::Wrong,
+            None => AnswerCheck::Unknown,
+        }
+    }
+
+    /// Record an answer in the answers file of a year.
+    pub fn record(year: Year, day: Day, part: u8, value: &str) -> Result<(), Error> {
+        let mut answers = Self::read_from_file(year);
+        answers.set(day, part, value);
+        answers.store_file(year)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AnswerCheck, Answers};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(3), 2, "42");
+        answers.set(day!(1), 1, "7");
+        answers.set(day!(3), 1, "41");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(3), 1), Some("41"));
+        assert_eq!(answers.get(day!(3), 2), Some("42"));
+        assert_eq!(answers.get(day!(1), 2), None);
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "7");
+
+        assert_eq!(answers.verify(day!(1), 1, "7"), AnswerCheck::Correct);
+        assert_eq!(answers.verify(day!(1), 1, "8"), AnswerCheck::Wrong);
+        assert_eq!(answers.verify(day!(1), 2, "8"), AnswerCheck::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "7");
+        answers.set(day!(2), 2, "multi\nline");
+
+        let json = JsonValue::from(answers).stringify().unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.get(day!(1), 1), Some("7"));
+        assert_eq!(parsed.get(day!(2), 2), Some("multi\nline"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26" }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+}
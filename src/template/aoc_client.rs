@@ -0,0 +1,340 @@
+/// Minimal Advent of Code HTTP client used to download inputs and puzzles and to submit answers.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use regex::Regex;
+
+use crate::template::{Day, Year, data_dir};
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    Request(String),
+    Io(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or write it to ~/.adventofcode.session."
+            ),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(value: io::Error) -> Self {
+        AocClientError::Io(value)
+    }
+}
+
+/// Authenticated client for a single event.
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Create a client for a year from the environment.
+    ///
+    /// The session cookie is read from `AOC_SESSION`, falling back to the files used by aoc-cli:
+    /// `~/.adventofcode.session` and `$XDG_CONFIG_HOME/adventofcode.session`.
+    /// `AOC_BASE_URL` can point the client to a different server.
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| session_files().find_map(|path| fs::read_to_string(path).ok()))
+            .filter(|x| !x.trim().is_empty())
+            .ok_or(AocClientError::SessionNotFound)?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| BASE_URL.into());
+
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    /// Fetch the personal puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!(
+            "{}/{}/day/{}/input",
+            self.base_url,
+            self.year,
+            day.into_inner()
+        ))
+    }
+
+    /// Fetch the description of a day as markdown. Contains part two once part one is solved.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            self.year,
+            day.into_inner()
+        ))?;
+        Ok(articles_to_markdown(&html))
+    }
+
+    /// Submit an answer and return the text of the response.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url,
+            self.year,
+            day.into_inner()
+        );
+
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(request_error)?
+            .into_string()?;
+
+        Ok(articles_to_markdown(&html))
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(request_error)?
+            .into_string()?)
+    }
+}
+
+/// Download input and puzzle description of a day, overwriting existing files.
+pub fn download(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = Client::from_env(year)?;
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    write_file(&input_path, &client.input(day)?)?;
+    write_file(&puzzle_path, &client.puzzle(day)?)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Download the puzzle description of a day and print it.
+pub fn read(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = Client::from_env(year)?;
+    let puzzle = client.puzzle(day)?;
+    write_file(&get_puzzle_path(year, day), &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+/// Submit an answer, print the response and return its text.
+pub fn submit(year: Year, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+    let response = Client::from_env(year)?.submit(day, part, answer)?;
+    println!("{response}");
+    Ok(response)
+}
+
+/// Convert the `<article>` elements of a page to markdown.
+fn articles_to_markdown(html: &str) -> String {
+    static ARTICLE: OnceLock<Regex> = OnceLock::new();
+    let article = ARTICLE.get_or_init(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+
+    article
+        .captures_iter(html)
+        .map(|c| html2md::parse_html(&c[1]).trim().to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn request_error(error: ureq::Error) -> AocClientError {
+    match error {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            AocClientError::Request(format!("server responded with {code}: {}", body.trim()))
+        }
+        ureq::Error::Transport(e) => AocClientError::Request(e.to_string()),
+    }
+}
+
+fn session_files() -> impl Iterator<Item = PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|home| home.join(".adventofcode.session")),
+        config.map(|config| config.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_input_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("inputs").join(format!("{day}.txt"))
+}
+
+fn get_puzzle_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("puzzles").join(format!("{day}.md"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Client;
+    use crate::template::submissions::{Hint, Verdict, parse_verdict};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    const PUZZLE_PAGE: &str = include_str!("fixtures/puzzle.html");
+    const CORRECT_PAGE: &str = include_str!("fixtures/submit_correct.html");
+    const TOO_HIGH_PAGE: &str = include_str!("fixtures/submit_too_high.html");
+
+    /// Serve one recorded response per connection and report the raw requests received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8_lossy(&content));
+                sender.send(request).unwrap();
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Recorded\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, receiver)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (url, requests) = serve(vec![(200, "1\n2\n3\n")]);
+        let client = Client::new(&url, "secret\n", year!(2025));
+
+        assert_eq!(client.input(day!(1)).unwrap(), "1\n2\n3\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2025/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let (url, requests) = serve(vec![(200, PUZZLE_PAGE)]);
+        let client = Client::new(&url, "secret", year!(2025));
+
+        let puzzle = client.puzzle(day!(1)).unwrap();
+        assert!(puzzle.starts_with("\\--- Day 1: Historian Hysteria ---"));
+        assert!(puzzle.contains("```\n3   4\n4   3\n"));
+        assert!(puzzle.contains("\\--- Part Two ---"));
+        assert!(!puzzle.contains("Support Advent of Code"));
+        assert!(
+            requests
+                .recv()
+                .unwrap()
+                .starts_with("GET /2025/day/1 HTTP/1.1")
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, requests) = serve(vec![(200, TOO_HIGH_PAGE), (200, CORRECT_PAGE)]);
+        let client = Client::new(&url, "secret", year!(2025));
+
+        let response = client.submit(day!(1), 2, "31").unwrap();
+        assert_eq!(
+            parse_verdict(&response),
+            (Verdict::Incorrect, Some(Hint::TooHigh))
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2025/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=31"));
+
+        let response = client.submit(day!(1), 2, "11").unwrap();
+        assert_eq!(parse_verdict(&response), (Verdict::Correct, None));
+    }
+
+    #[test]
+    fn reports_error_responses() {
+        let (url, _requests) = serve(vec![(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        )]);
+        let client = Client::new(&url, "secret", year!(2025));
+
+        let error = client.input(day!(25)).unwrap_err().to_string();
+        assert!(error.contains("404"));
+        assert!(error.contains("before it unlocks"));
+    }
+}
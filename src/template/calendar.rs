@@ -0,0 +1,289 @@
+/// Knows which puzzles an event has and when they unlock.
+use std::error::Error;
+use std::fmt::Display;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::template::{Day, Year, all_days};
+
+/// Puzzles unlock at midnight in this UTC offset (EST).
+pub const SERVER_UTC_OFFSET: i64 = -5;
+
+/// The first event with 12 instead of 25 puzzles.
+const FIRST_SHORT_EVENT: u16 = 2025;
+
+/// Number of puzzles of an event.
+pub fn puzzle_count(year: Year) -> u8 {
+    if year.into_inner() >= FIRST_SHORT_EVENT {
+        12
+    } else {
+        25
+    }
+}
+
+/// Every day of an event, in order.
+pub fn days(year: Year) -> impl Iterator<Item = Day> {
+    all_days().take(usize::from(puzzle_count(year)))
+}
+
+/// Returns the day if it is part of the event, an error otherwise.
+pub fn check_day(year: Year, day: Day) -> Result<Day, DayOutOfRangeError> {
+    if day.into_inner() <= puzzle_count(year) {
+        Ok(day)
+    } else {
+        Err(DayOutOfRangeError { year, day })
+    }
+}
+
+/// An error which is returned for days that are not part of an event.
+#[derive(Debug)]
+pub struct DayOutOfRangeError {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Error for DayOutOfRangeError {}
+
+impl Display for DayOutOfRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {} is not part of {}, which has {} puzzles",
+            self.day.into_inner(),
+            self.year,
+            puzzle_count(self.year)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Seconds since the unix epoch.
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+}
+
+/// Source of the current time. Can be replaced to wait for unlocks without actually waiting.
+pub trait Clock {
+    /// Seconds since the unix epoch.
+    fn now(&self) -> i64;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the operating system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Unix timestamp at which the puzzle of a day unlocks.
+pub fn unlock_time(year: Year, day: Day) -> i64 {
+    let midnight = days_from_civil(i64::from(year.into_inner()), 12, day.into_inner().into());
+    midnight * 86_400 - SERVER_UTC_OFFSET * 3600
+}
+
+/// Whether the puzzle of a day is available at the given unix timestamp.
+pub fn is_unlocked(year: Year, day: Day, now: i64) -> bool {
+    now >= unlock_time(year, day)
+}
+
+/// Format a number of seconds as a countdown, e.g. `1d 02:03:04`.
+pub fn format_countdown(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/// The puzzle that unlocked on the current server date, if an event is running.
+pub fn today(now: i64) -> Option<(Year, Day)> {
+    let (year, month, day) = civil_from_days((now + SERVER_UTC_OFFSET * 3600).div_euclid(86_400));
+
+    let year = Year::new(u16::try_from(year).ok()?)?;
+    let day = Day::new(u8::try_from(day).ok()?)?;
+
+    (month == 12 && check_day(year, day).is_ok()).then_some((year, day))
+}
+
+/// The next puzzle of the current event that is not unlocked yet at the given timestamp.
+pub fn next_unlock(now: i64) -> Option<(Year, Day)> {
+    let (year, _, _) = civil_from_days((now + SERVER_UTC_OFFSET * 3600).div_euclid(86_400));
+    let year = Year::new(u16::try_from(year).ok()?)?;
+
+    days(year)
+        .find(|day| !is_unlocked(year, *day, now))
+        .map(|day| (year, day))
+}
+
+/// Sleep until a timestamp is reached, calling `on_tick` with the remaining seconds every second.
+pub fn wait_until(clock: &impl Clock, timestamp: i64, mut on_tick: impl FnMut(i64)) {
+    loop {
+        let remaining = timestamp - clock.now();
+
+        if remaining <= 0 {
+            break;
+        }
+
+        on_tick(remaining);
+        clock.sleep(Duration::from_secs(1));
+    }
+}
+
+/// Format a unix timestamp as a UTC date, e.g. `2025-12-01`.
+pub fn format_date(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86_400));
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// see: https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// A clock that starts at a fixed time and advances instantly when sleeping.
+#[cfg(feature = "test_lib")]
+pub(crate) struct FakeClock(pub std::cell::Cell<i64>);
+
+#[cfg(feature = "test_lib")]
+impl Clock for FakeClock {
+    fn now(&self) -> i64 {
+        self.0.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.0
+            .set(self.0.get() + i64::try_from(duration.as_secs()).unwrap());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        Clock, FakeClock, check_day, days, format_countdown, format_date, is_unlocked, next_unlock,
+        today, unlock_time, wait_until,
+    };
+    use crate::{day, year};
+    use std::cell::Cell;
+
+    // 2025-12-01T05:00:00Z, midnight EST.
+    const DEC_1_2025: i64 = 1_764_565_200;
+
+    #[test]
+    fn knows_puzzle_counts() {
+        assert_eq!(days(year!(2024)).count(), 25);
+        assert_eq!(days(year!(2025)).count(), 12);
+        assert_eq!(days(year!(2025)).last(), Some(day!(12)));
+    }
+
+    #[test]
+    fn rejects_days_outside_event() {
+        assert!(check_day(year!(2024), day!(25)).is_ok());
+        assert!(check_day(year!(2025), day!(12)).is_ok());
+
+        let error = check_day(year!(2025), day!(13)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 13 is not part of 2025, which has 12 puzzles"
+        );
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        assert_eq!(unlock_time(year!(2025), day!(1)), DEC_1_2025);
+        assert_eq!(unlock_time(year!(2025), day!(2)), DEC_1_2025 + 86_400);
+        assert!(!is_unlocked(year!(2025), day!(1), DEC_1_2025 - 1));
+        assert!(is_unlocked(year!(2025), day!(1), DEC_1_2025));
+    }
+
+    #[test]
+    fn finds_todays_puzzle() {
+        assert_eq!(today(DEC_1_2025 - 1), None);
+        assert_eq!(today(DEC_1_2025), Some((year!(2025), day!(1))));
+        assert_eq!(
+            today(DEC_1_2025 + 11 * 86_400 + 3600),
+            Some((year!(2025), day!(12)))
+        );
+        assert_eq!(today(DEC_1_2025 + 12 * 86_400), None);
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        assert_eq!(next_unlock(DEC_1_2025 - 60), Some((year!(2025), day!(1))));
+        assert_eq!(next_unlock(DEC_1_2025), Some((year!(2025), day!(2))));
+        assert_eq!(
+            next_unlock(DEC_1_2025 + 10 * 86_400 + 1),
+            Some((year!(2025), day!(12)))
+        );
+        assert_eq!(next_unlock(DEC_1_2025 + 11 * 86_400), None);
+    }
+
+    #[test]
+    fn waits_for_unlock() {
+        let clock = FakeClock(Cell::new(DEC_1_2025 - 3));
+        let mut ticks = vec![];
+
+        wait_until(&clock, unlock_time(year!(2025), day!(1)), |remaining| {
+            ticks.push(remaining);
+        });
+
+        assert_eq!(ticks, vec![3, 2, 1]);
+        assert_eq!(clock.now(), DEC_1_2025);
+
+        wait_until(&clock, DEC_1_2025 - 10, |_| panic!("should not wait"));
+        assert_eq!(clock.now(), DEC_1_2025);
+    }
+
+    #[test]
+    fn formats_countdowns() {
+        assert_eq!(format_countdown(0), "00:00:00");
+        assert_eq!(format_countdown(3723), "01:02:03");
+        assert_eq!(format_countdown(90_000), "1d 01:00:00");
+        assert_eq!(format_countdown(-5), "00:00:00");
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(DEC_1_2025), "2025-12-01");
+    }
+}
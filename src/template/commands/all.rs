@@ -1,5 +1,54 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::registry::Solution;
+use crate::template::report::{self, ReportOptions};
+use crate::template::run_multi::{DayResults, print_year_header, run_multi};
+use crate::template::runner::{RunOptions, Timeouts};
+use crate::template::{Year, calendar};
+
+pub fn handle(
+    solutions: &[&Solution],
+    years: &[Year],
+    is_release: bool,
+    timeouts: Timeouts,
+    jobs: usize,
+    resources: bool,
+    report: Option<&ReportOptions>,
+) {
+    let results: Vec<(Year, Vec<DayResults>)> = report::redirect_output(report, || {
+        years
+            .iter()
+            .enumerate()
+            .map(|(i, year)| {
+                if years.len() > 1 {
+                    if i > 0 {
+                        println!();
+                    }
+                    print_year_header(*year);
+                }
+
+                let results = run_multi(
+                    solutions,
+                    *year,
+                    &calendar::days(*year).collect(),
+                    is_release,
+                    &RunOptions {
+                        timeouts,
+                        resources,
+                        ..RunOptions::default()
+                    },
+                    jobs,
+                );
+
+                (*year, results)
+            })
+            .collect()
+    });
+
+    if let Some(report) = report
+        && let Err(e) = report.write(&results)
+    {
+        eprintln!("Failed to write report: {e}");
+        process::exit(1);
+    }
 }
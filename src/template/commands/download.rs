@@ -1,16 +1,80 @@
-use crate::template::{Day, aoc_cli};
-use std::process;
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    process,
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
+use crate::template::{Day, Year, aoc_client, calendar, data_dir, examples};
+
+pub fn handle(year: Year, day: Day) {
+    let now = calendar::now();
+
+    if !calendar::is_unlocked(year, day, now) {
         eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            "day {day} of {year} is not unlocked yet, it unlocks in {}.",
+            calendar::format_countdown(calendar::unlock_time(year, day) - now)
         );
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = aoc_client::download(year, day) {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     };
+
+    extract_examples(year, day);
+}
+
+/// Write the examples of the downloaded puzzle and offer to insert the example answers into the tests.
+fn extract_examples(year: Year, day: Day) {
+    let Ok(puzzle) = fs::read_to_string(data_dir(year).join("puzzles").join(format!("{day}.md")))
+    else {
+        return;
+    };
+
+    let examples = examples::extract(&puzzle);
+
+    match examples::write(year, day, &examples.inputs) {
+        Ok(paths) => {
+            for path in paths {
+                println!("🎄 Extracted example to \"{}\".", path.display());
+            }
+        }
+        Err(e) => eprintln!("Failed to write examples: {e}"),
+    }
+
+    let module_path = format!("src/bin/{year}-{day}.rs");
+
+    let Some(source) = fs::read_to_string(&module_path)
+        .ok()
+        .and_then(|source| examples::insert_answers(&source, &examples.answers))
+    else {
+        return;
+    };
+
+    if !io::stdin().is_terminal() {
+        return;
+    }
+
+    let answers = examples
+        .answers
+        .iter()
+        .map(|answer| answer.as_deref().unwrap_or("-"))
+        .collect::<Vec<_>>();
+
+    print!(
+        "Example answers are {} (part one) and {} (part two). Insert them into the tests of \"{module_path}\"? [y/N] ",
+        answers[0], answers[1]
+    );
+    io::stdout().flush().ok();
+
+    let mut reply = String::new();
+    if io::stdin().read_line(&mut reply).is_err() || !reply.trim().eq_ignore_ascii_case("y") {
+        return;
+    }
+
+    match fs::write(&module_path, source) {
+        Ok(()) => println!("Updated tests in \"{module_path}\"."),
+        Err(e) => eprintln!("Failed to update tests: {e}"),
+    }
 }
@@ -4,3 +4,6 @@ pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod verify;
@@ -1,9 +1,50 @@
-use std::process::{Command, Stdio};
+use std::{
+    env, fs,
+    path::Path,
+    process::{self, Command, Stdio},
+    thread,
+    time::Duration,
+};
 
-use crate::template::Day;
+use crate::template::protocol::{self, PartResult};
+use crate::template::run_multi;
+use crate::template::runner::{InputSource, RunOptions};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Year, named_inputs, watch};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// How often watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    record: bool,
+    input: &InputSource,
+) {
+    // compare all inputs of the day, unless a single input was asked for.
+    let is_single_run = submit_part.is_some() || record || dhat || *input != InputSource::Inputs;
+
+    if !is_single_run && !named_inputs(year, day).is_empty() {
+        run_multi::run_day(
+            &[],
+            year,
+            day,
+            release,
+            &RunOptions::default(),
+            &run_multi::Console::default(),
+        );
+        return;
+    }
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        format!("{year}-{day}"),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -23,8 +64,19 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if force {
+        cmd_args.push("--force".to_string());
+    }
+
+    if record {
+        cmd_args.push("--record".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .env("AOC_YEAR", year.to_string())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -32,3 +84,75 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Rerun a day whenever its binary, `src/lib.rs` or its data files change.
+pub fn watch(year: Year, day: Day, release: bool, test: bool, input: &InputSource) {
+    let result_path = env::temp_dir().join(format!("aoc-{}-{year}-{day}.jsonl", process::id()));
+    let mut previous: Option<Vec<PartResult>> = None;
+
+    loop {
+        let snapshot = watch::snapshot(year, day);
+
+        // clear the screen and move the cursor to the top left.
+        print!("\x1b[2J\x1b[H");
+        println!("{ANSI_BOLD}Watching day {day} of {year}{ANSI_RESET} (Ctrl-C to stop)\n");
+
+        if test {
+            let mut test_args = vec!["test".to_string(), "--quiet".to_string()];
+            if release {
+                test_args.push("--release".to_string());
+            }
+            test_args.extend(["--bin".to_string(), format!("{year}-{day}")]);
+            run_cargo(year, &test_args, None);
+        }
+
+        let mut run_args = vec![
+            "run".to_string(),
+            "--quiet".to_string(),
+            "--bin".to_string(),
+            format!("{year}-{day}"),
+        ];
+        if release {
+            run_args.push("--release".to_string());
+        }
+        run_args.push("--".to_string());
+        run_args.extend(input.to_args());
+
+        let _ = fs::remove_file(&result_path);
+        run_cargo(year, &run_args, Some(&result_path));
+
+        let results = protocol::read_results(&result_path).unwrap_or_else(|e| {
+            eprintln!("Failed to read results: {e}");
+            vec![]
+        });
+
+        if let Some(previous) = &previous {
+            let changes = watch::answer_changes(previous, &results);
+            println!();
+            if changes.is_empty() {
+                println!("{ANSI_ITALIC}Answers unchanged.{ANSI_RESET}");
+            } else {
+                changes.iter().for_each(|change| println!("{change}"));
+            }
+        }
+
+        previous = Some(results);
+
+        while watch::snapshot(year, day) == snapshot {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+fn run_cargo(year: Year, args: &[String], result_path: Option<&Path>) {
+    let mut cmd = Command::new("cargo");
+    cmd.args(args).env("AOC_YEAR", year.to_string());
+
+    if let Some(path) = result_path {
+        cmd.env(protocol::RESULT_FILE_ENV, path);
+    }
+
+    if let Err(e) = cmd.status() {
+        eprintln!("Failed to run cargo: {e}");
+    }
+}
@@ -1,19 +1,91 @@
 use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::registry::Solution;
+use crate::template::regression;
+use crate::template::report::{self, ReportOptions};
+use crate::template::run_multi::{DayResults, print_year_header, run_multi, to_timings};
+use crate::template::runner::{BenchOptions, RunOptions, Timeouts};
+use crate::template::stats::format_nanos;
+use crate::template::timings::{HistoryEntry, RunInfo, Timings};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Year, calendar, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    solutions: &[&Solution],
+    years: &[Year],
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench: BenchOptions,
+    check: bool,
+    threshold: f64,
+    timeouts: Timeouts,
+    memory: bool,
+    resources: bool,
+    report: Option<&ReportOptions>,
+) {
+    let mut failed_check = false;
+
+    let results: Vec<(Year, Vec<DayResults>)> = report::redirect_output(report, || {
+        years
+            .iter()
+            .enumerate()
+            .map(|(i, year)| {
+                if years.len() > 1 {
+                    if i > 0 {
+                        println!();
+                    }
+                    print_year_header(*year);
+                }
+
+                let (results, passed) = handle_year(
+                    solutions, *year, day, run_all, store, bench, check, threshold, timeouts,
+                    memory, resources,
+                );
+                failed_check |= !passed;
+
+                (*year, results)
+            })
+            .collect()
+    });
+
+    if let Some(report) = report
+        && let Err(e) = report.write(&results)
+    {
+        eprintln!("Failed to write report: {e}");
+        process::exit(1);
+    }
+
+    if failed_check {
+        process::exit(1);
+    }
+}
+
+/// Time the days of a single year. Returns the results and `false` if the benchmark check failed.
+#[allow(clippy::too_many_arguments)]
+fn handle_year(
+    solutions: &[&Solution],
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench: BenchOptions,
+    check: bool,
+    threshold: f64,
+    timeouts: Timeouts,
+    memory: bool,
+    resources: bool,
+) -> (Vec<DayResults>, bool) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                calendar::days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                calendar::days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,20 +93,121 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        is_timed: true,
+        bench,
+        timeouts,
+        memory,
+        resources,
+        ..RunOptions::default()
+    };
+
+    // timings are always measured with a release build, the runner starts release children if it is a debug build.
+    let is_release = true;
+    let results = run_multi(solutions, year, &days_to_run, is_release, &options, 1);
+    let timings = to_timings(&results);
+
+    let deltas = regression::compare(&stored_timings, &timings);
+    let regressions = regression::print_report(&deltas, threshold);
+
+    if check && regressions > 0 {
+        eprintln!("Benchmark check failed, timings of {year} were not stored.");
+        return (results, false);
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let profile = if is_release { "release" } else { "debug" };
+
+        let mut merged_timings = stored_timings.merge(&timings);
+        merged_timings.push_history(&timings, &RunInfo::collect(profile));
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
+
+        // tables without a year show the default year, other years only update their own tables.
+        match readme_benchmarks::update(year, profile, &merged_timings.primary()) {
+            Ok(0) => {
+                println!("Stored timings of {year}, there is no benchmark table for {year}.");
+            }
+            Ok(_) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    (results, true)
+}
+
+/// Print all stored measurements of a day, oldest first.
+pub fn handle_history(year: Year, day: Day) {
+    let timings = Timings::read_from_file(year);
+    let entries = timings.history_for(day);
+
+    if entries.is_empty() {
+        println!(
+            "No stored history for day {day} of {year}. Run `cargo time {day} --year {year} --store` to record one."
+        );
+        return;
+    }
+
+    // every input is tracked separately, the default input comes first.
+    let mut inputs: Vec<Option<&str>> = entries.iter().map(|e| e.timing.input.as_deref()).collect();
+    inputs.sort_unstable();
+    inputs.dedup();
+
+    for (i, input) in inputs.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        match input {
+            Some(name) => println!("{ANSI_BOLD}{year} Day {day} history ({name}){ANSI_RESET}"),
+            None => println!("{ANSI_BOLD}{year} Day {day} history{ANSI_RESET}"),
+        }
+
+        print_history(
+            entries
+                .iter()
+                .filter(|e| e.timing.input.as_deref() == input),
+        );
+    }
+}
+
+fn print_history<'a>(entries: impl Iterator<Item = &'a &'a HistoryEntry>) {
+    println!(
+        "{:<10}  {:<14}  {:<7}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "Date", "Commit", "Profile", "Part 1", "Part 2", "Total", "Change"
+    );
+
+    let mut previous_total: Option<f64> = None;
+
+    for entry in entries {
+        let timing = &entry.timing;
+
+        let change = match previous_total {
+            Some(previous) if previous > 0.0 => {
+                format!(
+                    "{:+.1}%",
+                    (timing.total_nanos - previous) / previous * 100.0
+                )
+            }
+            _ => "-".into(),
+        };
+
+        println!(
+            "{:<10}  {:<14}  {:<7}  {:>10}  {:>10}  {:>10}  {:>8}",
+            entry.info.date(),
+            entry.info.commit.as_deref().unwrap_or("-"),
+            entry.info.profile,
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+            format_nanos(timing.total_nanos),
+            change,
+        );
+
+        previous_total = Some(timing.total_nanos);
+    }
 }
@@ -0,0 +1,83 @@
+use std::io::{self, Write};
+use std::process;
+
+use crate::template::calendar::{self, Clock};
+use crate::template::commands::{download, read, scaffold};
+use crate::template::{Day, Year};
+
+/// Seconds to wait after an unlock before downloading, in case the local clock runs ahead.
+const UNLOCK_GRACE_SECONDS: i64 = 2;
+
+pub fn handle(clock: &impl Clock, wait: bool) {
+    let (year, day) = puzzle(clock, wait).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    scaffold::handle(year, day, false);
+    download::handle(year, day);
+    read::handle(year, day);
+}
+
+/// The puzzle of today. With `wait`, waits for the next unlock if today has no puzzle.
+fn puzzle(clock: &impl Clock, wait: bool) -> Result<(Year, Day), &'static str> {
+    if let Some(today) = calendar::today(clock.now()) {
+        return Ok(today);
+    }
+
+    if !wait {
+        return Err(
+            "`today` command can only be run while an event is running. \
+            Please use `scaffold` with a specific day, or pass `--wait` to wait for the next unlock.",
+        );
+    }
+
+    let (year, day) =
+        calendar::next_unlock(clock.now()).ok_or("No puzzle left to unlock in this event.")?;
+
+    let unlock_time = calendar::unlock_time(year, day) + UNLOCK_GRACE_SECONDS;
+
+    calendar::wait_until(clock, unlock_time, |remaining| {
+        print!(
+            "\r⏳ Day {day} of {year} unlocks in {} ",
+            calendar::format_countdown(remaining - UNLOCK_GRACE_SECONDS)
+        );
+        let _ = io::stdout().flush();
+    });
+
+    println!("\r🔓 Day {day} of {year} is unlocked!          ");
+    Ok((year, day))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{UNLOCK_GRACE_SECONDS, puzzle};
+    use crate::template::calendar::{self, Clock, FakeClock};
+    use crate::{day, year};
+    use std::cell::Cell;
+
+    // 2025-12-01T05:00:00Z, midnight EST.
+    const DEC_1_2025: i64 = 1_764_565_200;
+
+    #[test]
+    fn opens_unlocked_puzzle_without_waiting() {
+        let clock = FakeClock(Cell::new(DEC_1_2025 + 3 * 86_400 + 60));
+
+        assert_eq!(puzzle(&clock, true), Ok((year!(2025), day!(4))));
+        assert_eq!(clock.now(), DEC_1_2025 + 3 * 86_400 + 60);
+    }
+
+    #[test]
+    fn waits_for_next_unlock_between_events() {
+        let clock = FakeClock(Cell::new(DEC_1_2025 - 60));
+
+        assert_eq!(puzzle(&clock, true), Ok((year!(2025), day!(1))));
+        assert_eq!(
+            clock.now(),
+            calendar::unlock_time(year!(2025), day!(1)) + UNLOCK_GRACE_SECONDS
+        );
+
+        let clock = FakeClock(Cell::new(DEC_1_2025 - 60));
+        assert!(puzzle(&clock, false).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::answers::{AnswerCheck, Answers};
+use crate::template::registry::Solution;
+use crate::template::run_multi::run_multi;
+use crate::template::runner::RunOptions;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Year};
+
+/// Run days against their inputs and compare the answers with the known answers.
+/// Without a selection, every day with a known answer is verified.
+pub fn handle(solutions: &[&Solution], year: Year, days: Option<HashSet<Day>>, is_release: bool) {
+    let answers = Answers::read_from_file(year);
+    let days_to_run = days.unwrap_or_else(|| answers.data.iter().map(|a| a.day).collect());
+
+    if days_to_run.is_empty() {
+        println!("No known answers. Use `cargo solve <day> --record` to store answers.");
+        return;
+    }
+
+    let results = run_multi(
+        solutions,
+        year,
+        &days_to_run,
+        is_release,
+        &RunOptions::default(),
+        1,
+    );
+
+    let mut days: Vec<&Day> = days_to_run.iter().collect();
+    days.sort_unstable();
+
+    let (mut correct, mut failed, mut unknown) = (0, 0, 0);
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+
+    for day in days {
+        // known answers belong to the default input.
+        let day_results = results.iter().find(|r| r.day == *day && r.input.is_none());
+
+        for part in [1, 2] {
+            let known = answers.get(*day, part);
+            let answer = day_results
+                .and_then(|r| r.part(part))
+                .and_then(|r| r.answer.as_deref());
+
+            let status = match (answer, known) {
+                (Some(answer), _) => match answers.verify(*day, part, answer) {
+                    AnswerCheck::Correct => {
+                        correct += 1;
+                        "✔".into()
+                    }
+                    AnswerCheck::Wrong => {
+                        failed += 1;
+                        format!("✖ got {answer}, expected {}", known.unwrap_or_default())
+                    }
+                    AnswerCheck::Unknown => {
+                        unknown += 1;
+                        "? no known answer".into()
+                    }
+                },
+                (None, Some(known)) => {
+                    failed += 1;
+                    format!("✖ no answer, expected {known}")
+                }
+                (None, None) => continue,
+            };
+
+            println!("Day {day} Part {part}: {status}");
+        }
+    }
+
+    println!("{correct} correct, {failed} failed, {unknown} without known answer.");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
@@ -1,13 +1,8 @@
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
-#[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
-
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
-
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -37,20 +32,6 @@ impl Day {
     }
 }
 
-#[cfg(feature = "today")]
-impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
-    }
-}
-
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:02}", self.0)
@@ -92,6 +73,24 @@ impl Display for DayFromStrError {
     }
 }
 
+/// Parses a single day (`5`) or an inclusive range of days (`3-7` or `3..=7`).
+/// A range that ends before it starts is an error.
+pub fn parse_days(s: &str) -> Result<HashSet<Day>, DayFromStrError> {
+    let Some((start, end)) = s.split_once("..=").or_else(|| s.split_once('-')) else {
+        return Ok(HashSet::from([s.parse()?]));
+    };
+
+    let start: Day = start.parse()?;
+    let end: Day = end.parse()?;
+    if start > end {
+        return Err(DayFromStrError);
+    }
+
+    Ok(all_days()
+        .filter(|day| *day >= start && *day <= end)
+        .collect())
+}
+
 /* -------------------------------------------------------------------------- */
 
 /// An iterator that yields every day of advent from the 1st to the 25th.
@@ -143,7 +142,25 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{Day, all_days, parse_days};
+    use std::collections::HashSet;
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(parse_days("5").unwrap(), HashSet::from([Day(5)]));
+        assert_eq!(
+            parse_days("3-5").unwrap(),
+            HashSet::from([Day(3), Day(4), Day(5)])
+        );
+        assert_eq!(
+            parse_days("24..=25").unwrap(),
+            HashSet::from([Day(24), Day(25)])
+        );
+        assert!(parse_days("5-3").is_err());
+        assert!(parse_days("3..7").is_err());
+        assert!(parse_days("0-3").is_err());
+        assert!(parse_days("foo").is_err());
+    }
 
     #[test]
     fn all_days_iterator() {
@@ -0,0 +1,281 @@
+/// Extracts example inputs and answers from puzzle descriptions downloaded as markdown.
+use std::{fs, io, path::PathBuf, sync::OnceLock};
+
+use regex::Regex;
+
+use crate::template::{Day, Year, data_dir};
+
+/// Examples found in a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    /// Example inputs, most likely first.
+    pub inputs: Vec<String>,
+    /// Emphasized answer of the example, per part.
+    pub answers: [Option<String>; 2],
+}
+
+struct CodeBlock {
+    content: String,
+    /// Whether the paragraph before the block introduces it as an example.
+    is_example: bool,
+}
+
+/// Parse example inputs and answers from a puzzle description.
+///
+/// Code blocks introduced by a paragraph mentioning an "example" are considered example inputs,
+/// multi-line ones first. If there are none, the first code block is used.
+pub fn extract(markdown: &str) -> Examples {
+    let blocks = code_blocks(markdown);
+
+    let mut inputs: Vec<(usize, &CodeBlock)> = blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, block)| block.is_example)
+        .collect();
+
+    inputs.sort_by_key(|(position, block)| (!block.content.contains('\n'), *position));
+
+    let mut inputs: Vec<String> = inputs.into_iter().fold(vec![], |mut inputs, (_, block)| {
+        if !inputs.contains(&block.content) {
+            inputs.push(block.content.clone());
+        }
+        inputs
+    });
+
+    if inputs.is_empty()
+        && let Some(block) = blocks.first()
+    {
+        inputs.push(block.content.clone());
+    }
+
+    let (part_one, part_two) = match markdown.find("--- Part Two ---") {
+        Some(index) => markdown.split_at(index),
+        None => (markdown, ""),
+    };
+
+    Examples {
+        inputs,
+        answers: [last_answer(part_one), last_answer(part_two)],
+    }
+}
+
+fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut paragraph = String::new();
+    let mut is_paragraph_done = false;
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        if line.trim_start().starts_with("```") {
+            let content: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .collect();
+
+            let content = content.join("\n").trim_end().to_string();
+
+            if !content.is_empty() {
+                blocks.push(CodeBlock {
+                    content,
+                    is_example: paragraph.to_lowercase().contains("example"),
+                });
+            }
+
+            paragraph.clear();
+        } else if line.trim().is_empty() {
+            is_paragraph_done = true;
+        } else {
+            if is_paragraph_done {
+                paragraph.clear();
+                is_paragraph_done = false;
+            }
+            paragraph.push_str(line);
+            paragraph.push('\n');
+        }
+    }
+
+    blocks
+}
+
+/// Answers are highlighted as emphasized code, e.g. `` `*11*` `` or ``*`11`*``.
+fn last_answer(markdown: &str) -> Option<String> {
+    static ANSWER: OnceLock<Regex> = OnceLock::new();
+    let answer = ANSWER.get_or_init(|| Regex::new(r"`\*([^`*]+)\*`|\*`([^`]+)`\*").unwrap());
+
+    answer
+        .captures_iter(markdown)
+        .last()
+        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Write example inputs that are not yet present to the examples folder of a year.
+///
+/// The first new input fills `NN.txt` if that file is missing or empty, others go to the next free
+/// `NN-2.txt`, `NN-3.txt`, ... file. Returns the paths of the written files.
+pub fn write(year: Year, day: Day, inputs: &[String]) -> Result<Vec<PathBuf>, io::Error> {
+    let folder = data_dir(year).join("examples");
+    fs::create_dir_all(&folder)?;
+
+    let main_path = folder.join(format!("{day}.txt"));
+    let part_path = |n: u8| folder.join(format!("{day}-{n}.txt"));
+
+    let mut existing: Vec<String> = vec![];
+    let mut is_main_free = true;
+
+    if let Ok(content) = fs::read_to_string(&main_path)
+        && !content.trim().is_empty()
+    {
+        existing.push(content.trim_end().to_string());
+        is_main_free = false;
+    }
+
+    let mut next_part = 2;
+    while let Ok(content) = fs::read_to_string(part_path(next_part)) {
+        existing.push(content.trim_end().to_string());
+        next_part += 1;
+    }
+
+    let mut written = vec![];
+
+    for input in inputs.iter().filter(|input| !existing.contains(input)) {
+        let path = if is_main_free {
+            is_main_free = false;
+            main_path.clone()
+        } else {
+            next_part += 1;
+            part_path(next_part - 1)
+        };
+
+        fs::write(&path, input)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Replace the `None` assertions of the generated tests with example answers.
+///
+/// Only numeric answers are inserted, as the template's parts return `Option<u64>`.
+/// Returns `None` if nothing was replaced.
+pub fn insert_answers(source: &str, answers: &[Option<String>; 2]) -> Option<String> {
+    const PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+    let mut source = source.to_string();
+    let mut changed = false;
+
+    for (test, answer) in ["fn test_part_one()", "fn test_part_two()"]
+        .iter()
+        .zip(answers)
+    {
+        let Some(answer) = answer.as_ref().filter(|a| a.parse::<u64>().is_ok()) else {
+            continue;
+        };
+
+        let Some(start) = source.find(test) else {
+            continue;
+        };
+
+        let end = source[start..]
+            .find("\n    }")
+            .map_or(source.len(), |end| start + end);
+
+        if let Some(offset) = source[start..end].find(PLACEHOLDER) {
+            let index = start + offset;
+            source.replace_range(
+                index..index + PLACEHOLDER.len(),
+                &format!("assert_eq!(result, Some({answer}));"),
+            );
+            changed = true;
+        }
+    }
+
+    changed.then_some(source)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, insert_answers};
+
+    const PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+The *Chief Historian* is always present.
+
+Each location has an ID, like `17`.
+
+For example:
+
+```
+3   4
+4   3
+2   5
+
+```
+
+After pairing them up:
+
+```
+1   3
+2   3
+```
+
+In the example above, this is `2 + 1 + 0`, a total distance of `*11*`!
+
+\--- Part Two ---
+----------
+
+This time, consider this example:
+
+```
+a->b
+
+```
+
+So, for these example lists, the similarity score is `*31*` (`9 + 4`).";
+
+    const TEMPLATE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    #[test]
+    fn extracts_examples() {
+        let examples = extract(PUZZLE);
+        assert_eq!(examples.inputs, vec!["3   4\n4   3\n2   5", "a->b"]);
+        assert_eq!(
+            examples.answers,
+            [Some("11".to_string()), Some("31".to_string())]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_first_block() {
+        let examples = extract("Consider:\n\n```\nxyz\n```\n\nThe answer is *`5`*.");
+        assert_eq!(examples.inputs, vec!["xyz"]);
+        assert_eq!(examples.answers, [Some("5".to_string()), None]);
+    }
+
+    #[test]
+    fn handles_missing_examples() {
+        assert_eq!(extract("Nothing to see here."), Default::default());
+    }
+
+    #[test]
+    fn inserts_answers() {
+        let source = insert_answers(TEMPLATE, &[Some("11".into()), Some("31".into())]).unwrap();
+        let part_one = source.find("Some(11)").unwrap();
+        let part_two = source.find("Some(31)").unwrap();
+        assert!(source.find("fn test_part_one").unwrap() < part_one);
+        assert!(source.find("fn test_part_two").unwrap() < part_two);
+    }
+
+    #[test]
+    fn skips_non_numeric_answers() {
+        let source = insert_answers(TEMPLATE, &[None, Some("31".into())]).unwrap();
+        assert!(source.contains("assert_eq!(result, None);"));
+        assert!(source.contains("assert_eq!(result, Some(31));"));
+
+        assert_eq!(insert_answers(TEMPLATE, &[Some("abc".into()), None]), None);
+        assert_eq!(insert_answers(&source, &[None, Some("32".into())]), None);
+    }
+}
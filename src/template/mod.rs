@@ -1,27 +1,92 @@
-use std::{env, fs};
+use std::{env, fs, io, path::PathBuf};
 
-pub mod aoc_cli;
+#[cfg(feature = "count-allocs")]
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
+pub mod calendar;
 pub mod commands;
+pub mod protocol;
+pub mod registry;
+pub mod regression;
+pub mod report;
 pub mod runner;
+pub mod solver;
+pub mod stats;
+pub mod submissions;
 
 pub use day::*;
+pub use year::*;
 
 mod day;
+mod examples;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+// the allocators are installed here and not by `solution!`: the runner binary includes every
+// solution as a module, and a program can only have one global allocator.
+// dhat takes precedence, it tracks the same numbers.
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: alloc::CountingAlloc = alloc::CountingAlloc;
+
+/// Directory that holds the data of a year, e.g. `data/2025`.
+pub fn data_dir(year: Year) -> PathBuf {
+    PathBuf::from("data").join(year.to_string())
+}
+
+/// Helper function that reads a text file of the default year (`AOC_YEAR`) to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_year_file(folder, default_year(), day)
+}
+
+/// Helper function that reads a text file of a year to a string.
+#[must_use]
+pub fn read_year_file(folder: &str, year: Year, day: Day) -> String {
+    try_read_file(folder, year, day).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can not be read.
+pub fn try_read_file(folder: &str, year: Year, day: Day) -> Result<String, io::Error> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd
+        .join(data_dir(year))
+        .join(folder)
+        .join(format!("{day}.txt"));
+    fs::read_to_string(filepath)
+}
+
+/// Names of the inputs stored in the input folder of a day, e.g. `alice` for
+/// `data/2025/inputs/01/alice.txt`. Days can have several inputs, e.g. one per account.
+pub fn named_inputs(year: Year, day: Day) -> Vec<String> {
+    let folder = data_dir(year).join("inputs").join(day.to_string());
+
+    let mut names: Vec<String> = fs::read_dir(folder)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+
+    names.sort_unstable();
+    names
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -29,14 +94,20 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(default_year()))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+fn default_year() -> Year {
+    Year::from_env().expect("AOC_YEAR is not set to a valid year")
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+///
+/// The year is taken from the name of the binary (e.g. `2025-01`), falling back to `AOC_YEAR`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
 #[macro_export]
@@ -52,17 +123,52 @@ macro_rules! solution {
     };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $day);
+
+        /// The parts of this day, used by the runner binary to run it in-process.
+        #[allow(dead_code)]
+        pub static SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                solve: |input, options, results| {
+                    $(
+                        results.push($crate::template::runner::run_part(
+                            $func, input, YEAR, DAY, $part, options,
+                        ));
+                    )*
+                },
+            };
+
+        $crate::solution!(@main);
+    };
+
+    (@consts $day:expr) => {
+        /// The year of the current day.
+        #[allow(dead_code)]
+        const YEAR: $crate::template::Year = {
+            match $crate::template::Year::from_name(module_path!()) {
+                Some(year) => year,
+                None => match option_env!("AOC_YEAR") {
+                    Some(year) => $crate::template::Year::from_name(year)
+                        .expect("AOC_YEAR is not set to a valid year"),
+                    None => panic!("could not determine the year of this solution"),
+                },
+            }
+        };
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
+    };
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
-
+    (@main) => {
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = $crate::template::runner::RunOptions::from_args();
+            let input = options.input.read(YEAR, DAY).unwrap_or_else(|e| {
+                eprintln!("could not open input file: {e}");
+                std::process::exit(1);
+            });
+            (SOLUTION.solve)(&input, &options, &mut vec![]);
         }
     };
 }
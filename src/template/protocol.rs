@@ -0,0 +1,295 @@
+/// Machine-readable protocol between solution binaries and `run_multi`.
+///
+/// When the `AOC_RESULT_FILE` environment variable is set, every solution part appends one JSON record
+/// (a single line) to that file. This keeps the human-readable terminal output free to change.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::stats::{MemoryStats, Stats};
+
+pub const RESULT_FILE_ENV: &str = "AOC_RESULT_FILE";
+
+/// Part number under which solutions that parse their input once report the parse step.
+pub const PARSE_PART: u8 = 0;
+
+/// Human-readable name of a part, e.g. `Part 1` or `Parse`.
+pub fn part_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// Outcome of a single solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+}
+
+impl PartStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "failed" => Ok(PartStatus::Failed),
+            s => Err(format!("unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// Record emitted by a solution binary for every part it runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// Error message, present if the part failed.
+    pub error: Option<String>,
+    pub nanos: f64,
+    pub samples: u128,
+    /// Benchmark statistics, present if the part was benched.
+    pub stats: Option<Stats>,
+    /// Heap usage, present if the part ran with `--memory`.
+    pub memory: Option<MemoryStats>,
+}
+
+/// Append a record to the result file, if the parent process asked for one.
+pub fn emit(result: &PartResult) {
+    let Ok(path) = env::var(RESULT_FILE_ENV) else {
+        return;
+    };
+
+    if let Err(e) = append(Path::new(&path), result) {
+        eprintln!("Failed to write result record to \"{path}\": {e}");
+    }
+}
+
+fn append(path: &Path, result: &PartResult) -> Result<(), io::Error> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = JsonValue::from(result)
+        .stringify()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    writeln!(file, "{line}")
+}
+
+/// Read all records written to a result file. A missing file yields no records.
+pub fn read_results(path: &Path) -> Result<Vec<PartResult>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => parse_results(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse_results(content: &str) -> Result<Vec<PartResult>, String> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("not a valid JSON record."))?;
+            PartResult::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|x| x as u8)
+            .ok_or("Expected result.part to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected result.status to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        let error = match json.get("error") {
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected result.error to be a string.")?
+                    .clone(),
+            ),
+            None => None,
+        };
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected result.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|x| x as u128)
+            .ok_or("Expected result.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let memory = json.get("memory").map(MemoryStats::try_from).transpose()?;
+
+        Ok(PartResult {
+            part,
+            status,
+            answer: answer.cloned(),
+            error,
+            nanos,
+            samples,
+            stats,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartResult, PartStatus, parse_results};
+    use crate::template::stats::{MemoryStats, Stats};
+    use tinyjson::JsonValue;
+
+    fn get_mock_result() -> PartResult {
+        PartResult {
+            part: 1,
+            status: PartStatus::Solved,
+            answer: Some("1234 (samples)".into()),
+            error: None,
+            nanos: 74130.0,
+            samples: 100,
+            stats: Some(Stats {
+                min: 70000.0,
+                median: 74000.0,
+                mean: 74130.0,
+                std_dev: 1200.5,
+                p95: 80000.0,
+                samples: 100,
+            }),
+            memory: Some(MemoryStats {
+                total_bytes: 4096,
+                peak_bytes: 1024,
+                allocations: 3,
+            }),
+        }
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let result = get_mock_result();
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(parse_results(&line).unwrap(), vec![result]);
+    }
+
+    #[test]
+    fn parses_multiple_records() {
+        let content = [
+            r#"{ "part": 1, "status": "solved", "answer": "42", "nanos": 1000, "samples": 10 }"#,
+            "",
+            r#"{ "part": 2, "status": "unsolved", "answer": null, "nanos": 0, "samples": 1 }"#,
+        ]
+        .join("\n");
+        let results = parse_results(&content).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].answer, Some("42".into()));
+        assert_eq!(results[1].status, PartStatus::Unsolved);
+        assert_eq!(results[1].answer, None);
+        assert_eq!(results[1].stats, None);
+    }
+
+    #[test]
+    fn parses_failed_records() {
+        let content = r#"{ "part": 1, "status": "failed", "answer": null, "error": "bad range", "nanos": 10, "samples": 1 }"#;
+        let results = parse_results(content).unwrap();
+        assert_eq!(results[0].status, PartStatus::Failed);
+        assert_eq!(results[0].error, Some("bad range".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        parse_results(r#"{ "part": 1, "status": "maybe" }"#).unwrap();
+    }
+}
@@ -1,77 +1,423 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// A file can contain several tables, each between a pair of markers. The opening marker may
+/// configure its table, e.g. `<!--- benchmarking table year=2024 columns=day,total,percent sort=-total --->`.
+use std::{cmp::Ordering, env, fmt::Display, fs, io, str::FromStr};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::protocol::PARSE_PART;
+use crate::template::stats::{MemoryStats, ResourceUsage, Stats, format_bytes, format_nanos};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_START: &str = "<!--- benchmarking table";
+static MARKER_END: &str = "--->";
+
+/// File the tables are written to, unless overridden with `AOC_BENCHMARKS_FILE`.
+static DEFAULT_FILE: &str = "README.md";
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-pub struct TablePosition {
+/// A column of the table. Columns about single parts are shown for part 1 and part 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Day,
+    Parse,
+    Part1,
+    Part2,
+    Total,
+    Median,
+    StdDev,
+    Samples,
+    Memory,
+    Usage,
+    /// Change of the total time since the previous stored run.
+    Delta,
+    /// Share of the total time of all days.
+    Percent,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Column::Day),
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "median" => Ok(Column::Median),
+            "std_dev" => Ok(Column::StdDev),
+            "samples" => Ok(Column::Samples),
+            "memory" => Ok(Column::Memory),
+            "usage" => Ok(Column::Usage),
+            "delta" => Ok(Column::Delta),
+            "percent" => Ok(Column::Percent),
+            s => Err(format!("unknown column `{s}`.")),
+        }
+    }
+}
+
+/// Value the rows of a table are sorted by.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Day,
+    Total,
+    Part1,
+    Part2,
+    /// Largest heap peak of the parts.
+    Memory,
+    /// Peak resident set size of the process.
+    Rss,
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortKey::Day),
+            "total" => Ok(SortKey::Total),
+            "part_1" => Ok(SortKey::Part1),
+            "part_2" => Ok(SortKey::Part2),
+            "memory" => Ok(SortKey::Memory),
+            "rss" => Ok(SortKey::Rss),
+            s => Err(format!("unknown sort key `{s}`.")),
+        }
+    }
+}
+
+/// Options of a table, parsed from its opening marker.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableConfig {
+    /// Year the table shows. Tables without a year show the default year.
+    pub year: Option<Year>,
+    /// Only runs of this profile update the table.
+    pub profile: Option<String>,
+    /// Columns in order. If not set, the columns depend on what was measured.
+    pub columns: Option<Vec<Column>>,
+    pub sort: SortKey,
+    pub descending: bool,
+}
+
+impl FromStr for TableConfig {
+    type Err = String;
+
+    /// Parse space separated `key=value` options, e.g. `year=2024 columns=day,total sort=-total`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = TableConfig::default();
+
+        for option in s.split_whitespace() {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or(format!("expected `key=value`, found `{option}`."))?;
+
+            match key {
+                "year" => {
+                    config.year = Some(value.parse().map_err(|e| format!("{e}"))?);
+                }
+                "profile" => config.profile = Some(value.into()),
+                "columns" => {
+                    config.columns = Some(
+                        value
+                            .split(',')
+                            .map(Column::from_str)
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                "sort" => {
+                    let (key, descending) = match value.strip_prefix('-') {
+                        Some(key) => (key, true),
+                        None => (value, false),
+                    };
+                    config.sort = key.parse()?;
+                    config.descending = descending;
+                }
+                key => return Err(format!("unknown option `{key}`.")),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// A table between two markers.
+struct Section {
     pos_start: usize,
     pos_end: usize,
+    /// The opening marker, kept as is when the table is replaced.
+    marker: String,
+    config: TableConfig,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{year}-{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Find all tables. Markers are paired in order, the options of closing markers are ignored.
+fn locate_tables(readme: &str) -> Result<Vec<Section>, Error> {
+    let mut markers: Vec<(usize, usize)> = vec![];
+    let mut offset = 0;
+
+    while let Some(start) = readme[offset..].find(MARKER_START).map(|i| i + offset) {
+        let end = readme[start..]
+            .find(MARKER_END)
+            .map(|i| start + i + MARKER_END.len())
+            .ok_or_else(|| Error::Parser("Could not find end of marker.".into()))?;
 
-    if matches.len() > 2 {
+        markers.push((start, end));
+        offset = end;
+    }
+
+    if markers.is_empty() {
+        return Err(Error::Parser("Could not find table start position.".into()));
+    }
+
+    if !markers.len().is_multiple_of(2) {
         return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+            "Markers in README must come in pairs, found an unpaired marker.".into(),
         ));
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    markers
+        .chunks(2)
+        .map(|pair| {
+            let (start, marker_end) = pair[0];
+            let marker = &readme[start..marker_end];
+            let options = &marker[MARKER_START.len()..marker.len() - MARKER_END.len()];
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+            Ok(Section {
+                pos_start: start,
+                pos_end: pair[1].1,
+                marker: marker.into(),
+                config: options.parse().map_err(Error::Parser)?,
+            })
+        })
+        .collect()
+}
 
-    Ok(TablePosition { pos_start, pos_end })
+/// The columns of a table without explicit columns: the parse column only if a solution times its
+/// parse step, memory columns only if the heap usage was measured and resource columns only if
+/// the days ran with `--resources`.
+fn default_columns(timings: &Timings) -> Vec<Column> {
+    let mut columns = vec![Column::Day];
+    if timings.data.iter().any(|t| t.parse.is_some()) {
+        columns.push(Column::Parse);
+    }
+    columns.extend([Column::Part1, Column::Part2]);
+    if timings.data.iter().any(Timing::has_memory) {
+        columns.push(Column::Memory);
+    }
+    if timings.data.iter().any(|t| t.usage.is_some()) {
+        columns.push(Column::Usage);
+    }
+    columns
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn headers(column: Column) -> Vec<&'static str> {
+    match column {
+        Column::Day => vec!["Day"],
+        Column::Parse => vec!["Parse"],
+        Column::Part1 => vec!["Part 1"],
+        Column::Part2 => vec!["Part 2"],
+        Column::Total => vec!["Total"],
+        Column::Median => vec!["Part 1 median", "Part 2 median"],
+        Column::StdDev => vec!["Part 1 σ", "Part 2 σ"],
+        Column::Samples => vec!["Part 1 samples", "Part 2 samples"],
+        Column::Memory => vec!["Part 1 memory", "Part 2 memory"],
+        Column::Usage => vec!["Max RSS", "CPU (user / sys)"],
+        Column::Delta => vec!["Change"],
+        Column::Percent => vec!["Share"],
+    }
+}
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+fn cells(
+    column: Column,
+    year: Year,
+    profile: &str,
+    timing: &Timing,
+    timings: &Timings,
+) -> Vec<String> {
+    let time = |value: &Option<String>| format!("`{}`", value.as_deref().unwrap_or("-"));
+    let per_part = |format: &dyn Fn(Option<Stats>) -> String| {
+        vec![format(timing.part_1_stats), format(timing.part_2_stats)]
+    };
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+    match column {
+        Column::Day => vec![format!(
+            "[Day {}]({})",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            get_path_for_bin(year, timing.day)
+        )],
+        Column::Parse => vec![time(&timing.parse)],
+        Column::Part1 => vec![time(&timing.part_1)],
+        Column::Part2 => vec![time(&timing.part_2)],
+        Column::Total => vec![format!("`{}`", format_nanos(timing.total_nanos))],
+        Column::Median => per_part(&|stats| format_stat(stats, |s| format_nanos(s.median))),
+        Column::StdDev => per_part(&|stats| format_stat(stats, |s| format_nanos(s.std_dev))),
+        Column::Samples => per_part(&|stats| {
+            format_stat(stats.filter(|s| s.samples > 0), |s| s.samples.to_string())
+        }),
+        Column::Memory => [1, 2]
+            .map(|part| format_memory(timing.part_memory(part)))
+            .into(),
+        Column::Usage => format_usage(timing.usage).into(),
+        Column::Delta => vec![format_delta(timing, timings, profile)],
+        Column::Percent => vec![format_percent(timing, timings)],
+    }
+}
+
+fn format_stat(stats: Option<Stats>, format: impl Fn(Stats) -> String) -> String {
+    match stats {
+        Some(stats) => format!("`{}`", format(stats)),
+        None => "-".into(),
+    }
+}
+
+fn format_memory(memory: Option<MemoryStats>) -> String {
+    match memory {
+        Some(memory) => format!(
+            "`{}` ({} allocs)",
+            format_bytes(memory.peak_bytes),
+            memory.allocations
+        ),
+        None => "-".into(),
+    }
+}
+
+fn format_usage(usage: Option<ResourceUsage>) -> [String; 2] {
+    match usage {
+        Some(usage) => [
+            format!("`{}`", format_bytes(usage.peak_rss_bytes)),
+            format!(
+                "`{}` / `{}`",
+                format_nanos(usage.user_nanos),
+                format_nanos(usage.system_nanos)
+            ),
+        ],
+        None => ["-".into(), "-".into()],
+    }
+}
+
+/// Change of the total time compared to the run before the latest stored run of the day.
+/// Only runs of the same profile are compared.
+fn format_delta(timing: &Timing, timings: &Timings, profile: &str) -> String {
+    let history: Vec<f64> = timings
+        .history_for(timing.day)
+        .into_iter()
+        .filter(|e| e.timing.input == timing.input && e.info.profile == profile)
+        .map(|e| e.timing.total_nanos)
+        .collect();
+
+    match history.as_slice() {
+        [.., previous, _] if *previous > 0.0 => format!(
+            "{:+.1}%",
+            (timing.total_nanos - previous) / previous * 100.0
+        ),
+        _ => "-".into(),
+    }
+}
+
+fn format_percent(timing: &Timing, timings: &Timings) -> String {
+    let total = timings.data.iter().map(|t| t.total_nanos).sum::<f64>();
+
+    if total > 0.0 {
+        format!("{:.1}%", timing.total_nanos / total * 100.0)
+    } else {
+        "-".into()
+    }
+}
+
+fn sort_value(key: SortKey, timing: &Timing) -> Option<f64> {
+    #[allow(clippy::cast_precision_loss)]
+    match key {
+        SortKey::Day => Some(f64::from(timing.day.into_inner())),
+        SortKey::Total => Some(timing.total_nanos),
+        SortKey::Part1 => timing.part_nanos(1),
+        SortKey::Part2 => timing.part_nanos(2),
+        SortKey::Memory => [PARSE_PART, 1, 2]
+            .into_iter()
+            .filter_map(|part| timing.part_memory(part))
+            .map(|memory| memory.peak_bytes as f64)
+            .reduce(f64::max),
+        SortKey::Rss => timing.usage.map(|usage| usage.peak_rss_bytes as f64),
+    }
+}
+
+/// Sort the rows of a table. Days without a value come last, ties are sorted by day.
+fn sort_timings(timings: &mut [Timing], config: &TableConfig) {
+    timings.sort_by(|a, b| {
+        let order = match (sort_value(config.sort, a), sort_value(config.sort, b)) {
+            (Some(a), Some(b)) if config.descending => b.total_cmp(&a),
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        order.then(a.day.cmp(&b.day))
+    });
+}
+
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    config: &TableConfig,
+    year: Year,
+    profile: &str,
+    timings: &Timings,
+    total_millis: f64,
+) -> String {
+    let header = match (config.year, &config.profile) {
+        (Some(year), Some(profile)) => format!("{prefix} Benchmarks ({year}, {profile})"),
+        (Some(year), None) => format!("{prefix} Benchmarks ({year})"),
+        (None, Some(profile)) => format!("{prefix} Benchmarks ({profile})"),
+        (None, None) => format!("{prefix} Benchmarks"),
+    };
+
+    let columns = config
+        .columns
+        .clone()
+        .unwrap_or_else(|| default_columns(timings));
+    let titles: Vec<&str> = columns.iter().flat_map(|c| headers(*c)).collect();
+
+    let mut lines: Vec<String> = vec![marker.into(), header, String::new()];
+
+    lines.push(format!("| {} |", titles.join(" | ")));
+    lines.push(format!("|{} :---:  |", " :---: |".repeat(titles.len() - 1)));
+
+    let mut rows = timings.data.clone();
+    sort_timings(&mut rows, config);
+
+    for timing in &rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .flat_map(|c| cells(*c, year, profile, timing, timings))
+            .collect();
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
@@ -81,78 +427,128 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+/// Replace every table that shows `year` and accepts runs of `profile`.
+/// Tables without a year show `default_year`. Returns the number of updated tables.
+fn update_content(
+    s: &mut String,
+    year: Year,
+    default_year: Option<Year>,
+    profile: &str,
+    timings: &Timings,
+    total_millis: f64,
+) -> Result<usize, Error> {
+    let sections: Vec<Section> = locate_tables(s)?
+        .into_iter()
+        .filter(|section| section.config.year.or(default_year) == Some(year))
+        .filter(|section| section.config.profile.as_ref().is_none_or(|p| p == profile))
+        .collect();
+
+    // replace from the back, so that the positions of earlier tables stay valid.
+    for section in sections.iter().rev() {
+        let table = construct_table(
+            "##",
+            &section.marker,
+            &section.config,
+            year,
+            profile,
+            timings,
+            total_millis,
+        );
+        s.replace_range(section.pos_start..section.pos_end, &table);
+    }
+
+    Ok(sections.len())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+/// Update the benchmark tables of a year in `README.md`, or in the file set by
+/// `AOC_BENCHMARKS_FILE`. Returns the number of updated tables.
+pub fn update(year: Year, profile: &str, timings: &Timings) -> Result<usize, Error> {
+    let path = env::var("AOC_BENCHMARKS_FILE").unwrap_or_else(|_| DEFAULT_FILE.into());
+    let mut readme = String::from_utf8_lossy(&fs::read(&path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+    let updated = update_content(
+        &mut readme,
+        year,
+        Year::from_env(),
+        profile,
+        timings,
+        total_millis,
+    )?;
+    fs::write(&path, &readme)?;
+    Ok(updated)
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{Column, Error, MARKER, MARKER_START, SortKey, TableConfig, update_content};
+    use crate::template::stats::{MemoryStats, ResourceUsage, Stats};
+    use crate::template::timings::{HistoryEntry, RunInfo};
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
+    /// Update the tables of the default year 2025 with a release run.
+    fn update_readme(s: &mut String, timings: &Timings, total_millis: f64) -> Result<usize, Error> {
+        update_content(
+            s,
+            year!(2025),
+            Some(year!(2025)),
+            "release",
+            timings,
+            total_millis,
+        )
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_readme(&mut s, &get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_readme(&mut s, &get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_readme(&mut s, &get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_readme(&mut s, &get_mock_timings(), 190.0).unwrap();
+        update_readme(&mut s, &get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +556,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_readme(&mut s, &get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +565,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2025-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2025-02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2025-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +576,155 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_readme(&mut s, &timings, 195.0).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2025-01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2025-02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryStats {
+            total_bytes: 4096,
+            peak_bytes: 2048,
+            allocations: 3,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_readme(&mut s, &timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |"));
+        assert!(s.contains("| :---: | :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2025-01.rs) | `10ms` | `20ms` | `2.0 KiB` (3 allocs) | - |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/2025-02.rs) | `30ms` | `40ms` | - | - |"));
+    }
+
+    #[test]
+    fn format_usage_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[1].usage = Some(ResourceUsage {
+            peak_rss_bytes: 12 * 1024 * 1024,
+            user_nanos: 45_000_000.0,
+            system_nanos: 3_000_000.0,
+            wall_nanos: 20_000_000.0,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_readme(&mut s, &timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Max RSS | CPU (user / sys) |"));
+        assert!(s.contains("| [Day 1](./src/bin/2025-01.rs) | `10ms` | `20ms` | - | - |"));
+        assert!(s.contains(
+            "| [Day 2](./src/bin/2025-02.rs) | `30ms` | `40ms` | `12.0 MiB` | `45.0ms` / `3.0ms` |"
+        ));
+    }
+
+    #[test]
+    fn parses_table_config() {
+        let config: TableConfig = " year=2024 profile=debug columns=day,total,percent sort=-total "
+            .parse()
+            .unwrap();
+        assert_eq!(config.year, Some(year!(2024)));
+        assert_eq!(config.profile.as_deref(), Some("debug"));
+        assert_eq!(
+            config.columns,
+            Some(vec![Column::Day, Column::Total, Column::Percent])
+        );
+        assert_eq!(config.sort, SortKey::Total);
+        assert!(config.descending);
+
+        assert_eq!("".parse::<TableConfig>(), Ok(TableConfig::default()));
+        assert!("columns=day,speed".parse::<TableConfig>().is_err());
+        assert!("year".parse::<TableConfig>().is_err());
+    }
+
+    #[test]
+    fn format_configured_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(Stats {
+            min: 9e6,
+            median: 9.5e6,
+            mean: 1e7,
+            std_dev: 5e5,
+            p95: 1.2e7,
+            samples: 100,
+        });
+        // the debug run in between is not compared with release runs.
+        timings.history = [(4e10, "release"), (1e10, "debug"), (3e10, "release")]
+            .map(|(total_nanos, profile)| HistoryEntry {
+                timing: Timing {
+                    total_nanos,
+                    ..timings.data[0].clone()
+                },
+                info: RunInfo {
+                    timestamp: 0,
+                    commit: None,
+                    profile: profile.into(),
+                },
+            })
+            .into();
+
+        let marker =
+            "<!--- benchmarking table columns=day,median,samples,delta,percent sort=-total --->";
+        let mut s = format!("{marker}\n{MARKER}");
+        update_readme(&mut s, &timings, 190.0).unwrap();
+
+        let expected = [
+            marker,
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 median | Part 2 median | Part 1 samples | Part 2 samples | Change | Share |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 4](./src/bin/2025-04.rs) | - | - | - | - | - | 47.4% |",
+            "| [Day 2](./src/bin/2025-02.rs) | - | - | - | - | - | 36.8% |",
+            "| [Day 1](./src/bin/2025-01.rs) | `9.5ms` | - | `100` | - | -25.0% | 15.8% |",
+            "",
+            "**Total: 190.00ms**",
+            MARKER,
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_matching_sections() {
+        let mut s = [
+            "<!--- benchmarking table year=2024 --->",
+            MARKER,
+            "<!--- benchmarking table --->",
+            MARKER,
+            "<!--- benchmarking table profile=debug --->",
+            MARKER,
+        ]
+        .join("\n");
+
+        let updated = update_readme(&mut s, &get_mock_timings(), 190.0).unwrap();
+        assert_eq!(updated, 1);
+        assert_eq!(s.matches("## Benchmarks").count(), 1);
+
+        let updated = update_content(
+            &mut s,
+            year!(2024),
+            Some(year!(2025)),
+            "release",
+            &get_mock_timings(),
+            190.0,
+        )
+        .unwrap();
+        assert_eq!(updated, 1);
+        assert!(s.starts_with("<!--- benchmarking table year=2024 --->\n## Benchmarks (2024)\n"));
+        assert_eq!(s.matches("## Benchmarks").count(), 2);
+        assert_eq!(s.matches(MARKER_START).count(), 6);
+    }
 }
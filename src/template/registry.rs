@@ -0,0 +1,40 @@
+/// Solutions compiled into the runner binary.
+///
+/// Every `solution!` and `solver!` invocation exposes a [`Solution`] static. A build script collects them from `src/bin`
+/// so that `cargo all` and `cargo time` can execute all days in a single process.
+use crate::template::protocol::PartResult;
+use crate::template::runner::RunOptions;
+use crate::template::{Day, Year};
+
+/// A day that was registered by `solution!` or `solver!`.
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// Runs the parts of the day against an input, pushing the result of each part once it finished.
+    pub solve: fn(&str, &RunOptions, &mut Vec<PartResult>),
+}
+
+impl Solution {
+    /// Run the day against the input selected by the options, pushing the result of each part once
+    /// it finished. Parts that finished before a later part panics are kept.
+    /// Pushes no results if the input file can not be read.
+    pub fn run(&self, options: &RunOptions, results: &mut Vec<PartResult>) {
+        let input = match options.input.read(self.year, self.day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("could not open input file: {e}");
+                return;
+            }
+        };
+
+        (self.solve)(&input, options, results);
+    }
+}
+
+/// Look up the solution registered for a day of a year.
+pub fn find<'a>(solutions: &[&'a Solution], year: Year, day: Day) -> Option<&'a Solution> {
+    solutions
+        .iter()
+        .find(|s| s.year == year && s.day == day)
+        .copied()
+}
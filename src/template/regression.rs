@@ -0,0 +1,193 @@
+/// Compares fresh benchmark results with stored timings to detect regressions.
+use crate::template::protocol::{PARSE_PART, part_label};
+use crate::template::stats::format_nanos;
+use crate::template::timings::Timings;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day};
+
+/// Default slowdown, in percent, above which a part counts as a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Change in runtime of a single part (or of the parse step) between a stored and a fresh measurement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    /// Name of the input, `None` for the default input.
+    pub input: Option<String>,
+    pub part: u8,
+    pub before_nanos: f64,
+    pub after_nanos: f64,
+}
+
+impl PartDelta {
+    /// Relative change in percent. Positive values are slowdowns.
+    pub fn change_percent(&self) -> f64 {
+        (self.after_nanos - self.before_nanos) / self.before_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
+    }
+}
+
+/// Compute per-part deltas for all parts that are present in both sets of timings.
+pub fn compare(stored: &Timings, fresh: &Timings) -> Vec<PartDelta> {
+    fresh
+        .data
+        .iter()
+        .filter_map(|after| {
+            let before = stored
+                .data
+                .iter()
+                .find(|t| t.day == after.day && t.input == after.input)?;
+            Some((before, after))
+        })
+        .flat_map(|(before, after)| {
+            [PARSE_PART, 1, 2].into_iter().filter_map(|part| {
+                Some(PartDelta {
+                    day: after.day,
+                    input: after.input.clone(),
+                    part,
+                    before_nanos: before.part_nanos(part).filter(|x| *x > 0.0)?,
+                    after_nanos: after.part_nanos(part)?,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Print a line per part and return the number of regressions.
+pub fn print_report(deltas: &[PartDelta], threshold: f64) -> usize {
+    if deltas.is_empty() {
+        return 0;
+    }
+
+    println!("\n{ANSI_BOLD}Compared to stored timings{ANSI_RESET}");
+
+    for delta in deltas {
+        let change = delta.change_percent();
+        let verdict = if change < 0.0 { "faster" } else { "slower" };
+        let flag = if delta.is_regression(threshold) {
+            " ⚠ regression"
+        } else {
+            ""
+        };
+
+        let input = delta
+            .input
+            .as_deref()
+            .map(|name| format!(" ({name})"))
+            .unwrap_or_default();
+
+        println!(
+            "Day {}{input} {}: {} → {} ({change:+.1}%, {verdict}){flag}",
+            delta.day,
+            part_label(delta.part),
+            format_nanos(delta.before_nanos),
+            format_nanos(delta.after_nanos),
+        );
+    }
+
+    let regressions = deltas.iter().filter(|d| d.is_regression(threshold)).count();
+
+    if regressions > 0 {
+        println!("{regressions} part(s) regressed by more than {threshold}%.");
+    }
+
+    regressions
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::compare;
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn timing(day: u8, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            ..Timing::new(crate::template::Day::new(day).unwrap())
+        }
+    }
+
+    #[test]
+    fn detects_slowdowns_and_speedups() {
+        let stored = Timings {
+            data: vec![timing(1, Some("10.0ms"), Some("20.0ms"))],
+            history: vec![],
+        };
+        let fresh = Timings {
+            data: vec![timing(1, Some("30.0ms"), Some("10.0ms"))],
+            history: vec![],
+        };
+
+        let deltas = compare(&stored, &fresh);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].change_percent(), 200.0);
+        assert!(deltas[0].is_regression(10.0));
+        assert_eq!(deltas[1].change_percent(), -50.0);
+        assert!(!deltas[1].is_regression(10.0));
+    }
+
+    #[test]
+    fn respects_threshold() {
+        let stored = Timings {
+            data: vec![timing(1, Some("10.0ms"), None)],
+            history: vec![],
+        };
+        let fresh = Timings {
+            data: vec![timing(1, Some("10.5ms"), None)],
+            history: vec![],
+        };
+
+        let deltas = compare(&stored, &fresh);
+        assert!(deltas[0].is_regression(1.0));
+        assert!(!deltas[0].is_regression(10.0));
+    }
+
+    #[test]
+    fn skips_parts_without_stored_timing() {
+        let stored = Timings {
+            data: vec![timing(1, None, Some("1.0ms"))],
+            history: vec![],
+        };
+        let fresh = Timings {
+            data: vec![
+                timing(1, Some("1.0ms"), Some("1.0ms")),
+                timing(2, Some("1.0ms"), Some("1.0ms")),
+            ],
+            history: vec![],
+        };
+
+        let deltas = compare(&stored, &fresh);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].part, 2);
+    }
+
+    #[test]
+    fn compares_inputs_separately() {
+        let named = |name: &str, part_1: &str| Timing {
+            input: Some(name.into()),
+            ..timing(1, Some(part_1), None)
+        };
+
+        let stored = Timings {
+            data: vec![named("alice", "10.0ms"), named("bob", "20.0ms")],
+            history: vec![],
+        };
+        let fresh = Timings {
+            data: vec![named("bob", "10.0ms"), named("alice", "20.0ms")],
+            history: vec![],
+        };
+
+        let deltas = compare(&stored, &fresh);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].input.as_deref(), Some("bob"));
+        assert_eq!(deltas[0].change_percent(), -50.0);
+        assert_eq!(deltas[1].input.as_deref(), Some("alice"));
+        assert_eq!(deltas[1].change_percent(), 100.0);
+    }
+}
@@ -0,0 +1,549 @@
+/// Machine-readable reports of the results of `all` and `time`, e.g. for spreadsheets or CI test reports.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::protocol::{PartStatus, part_label};
+use crate::template::run_multi::{DayResults, RunStatus};
+use crate::template::stats::{MemoryStats, Stats, format_nanos};
+use crate::template::{Day, Year};
+
+/// Format of a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+    Junit,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "junit" => Ok(Format::Junit),
+            s => Err(format!(
+                "unknown format `{s}`, expected json, csv, markdown or junit."
+            )),
+        }
+    }
+}
+
+/// Which report to write and where to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReportOptions {
+    pub format: Format,
+    /// File the report is written to. Without a file, the report is written to stdout.
+    pub output: Option<PathBuf>,
+}
+
+impl ReportOptions {
+    /// Parse `--format <format>` and `--output <file>`. Returns `None` if no report was requested.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Option<Self>, pico_args::Error> {
+        let format: Option<Format> = args.opt_value_from_str("--format")?;
+        let output: Option<PathBuf> = args.opt_value_from_str("--output")?;
+
+        match (format, output) {
+            (Some(format), output) => Ok(Some(Self { format, output })),
+            (None, None) => Ok(None),
+            (None, Some(_)) => Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "--output requires --format".into(),
+            }),
+        }
+    }
+
+    /// Render the report and write it to its destination.
+    pub fn write(&self, results: &[(Year, Vec<DayResults>)]) -> Result<(), io::Error> {
+        let report = render(self.format, results);
+
+        match &self.output {
+            Some(path) => fs::write(path, report),
+            None => io::stdout().write_all(report.as_bytes()),
+        }
+    }
+}
+
+/// Run `f` while everything printed to stdout goes to stderr, if the report is written to stdout.
+/// This keeps stdout free for the report.
+pub fn redirect_output<T>(report: Option<&ReportOptions>, f: impl FnOnce() -> T) -> T {
+    if report.is_none_or(|report| report.output.is_some()) {
+        return f();
+    }
+
+    #[cfg(unix)]
+    {
+        let _ = io::stdout().flush();
+
+        // SAFETY: `dup` has no memory-safety preconditions.
+        let stdout = unsafe { libc::dup(libc::STDOUT_FILENO) };
+        if stdout == -1 {
+            // stdout could not be restored afterwards, so it is not redirected.
+            return f();
+        }
+
+        let _restore = RestoreStdout(stdout);
+
+        // SAFETY: `dup2` has no memory-safety preconditions. if it fails, stdout stays as it was.
+        if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } == -1 {
+            eprintln!("Could not redirect output: {}", io::Error::last_os_error());
+        }
+
+        f()
+    }
+
+    #[cfg(not(unix))]
+    f()
+}
+
+/// Points stdout back to a saved file descriptor when dropped, also if the redirected code panics.
+#[cfg(unix)]
+struct RestoreStdout(libc::c_int);
+
+#[cfg(unix)]
+impl Drop for RestoreStdout {
+    fn drop(&mut self) {
+        let _ = io::stdout().flush();
+
+        // SAFETY: the guard owns the saved file descriptor and closes it exactly once.
+        unsafe {
+            if libc::dup2(self.0, libc::STDOUT_FILENO) == -1 {
+                eprintln!("Could not restore output: {}", io::Error::last_os_error());
+            }
+            libc::close(self.0);
+        }
+    }
+}
+
+/// A line of a report: a part of a day, or a day that did not run to completion.
+struct Row<'a> {
+    year: Year,
+    day: Day,
+    input: Option<&'a str>,
+    /// `None` for a day that did not run to completion.
+    part: Option<u8>,
+    status: RowStatus<'a>,
+    answer: Option<&'a str>,
+    error: Option<String>,
+    nanos: Option<f64>,
+    samples: Option<u128>,
+    stats: Option<&'a Stats>,
+    memory: Option<MemoryStats>,
+}
+
+/// Status of a part, or of a day that did not run to completion.
+#[derive(Clone, Copy)]
+enum RowStatus<'a> {
+    Part(PartStatus),
+    Run(&'a RunStatus),
+}
+
+impl RowStatus<'_> {
+    fn as_str(self) -> &'static str {
+        match self {
+            RowStatus::Part(status) => status.as_str(),
+            RowStatus::Run(status) => status.as_str(),
+        }
+    }
+}
+
+impl Row<'_> {
+    fn label(&self) -> String {
+        self.part.map_or_else(|| "Run".into(), part_label)
+    }
+}
+
+fn rows(results: &[(Year, Vec<DayResults>)]) -> Vec<Row<'_>> {
+    results
+        .iter()
+        .flat_map(|(year, days)| days.iter().map(move |result| (*year, result)))
+        .flat_map(|(year, result)| {
+            let parts = result.parts.iter().map(move |p| Row {
+                year,
+                day: result.day,
+                input: result.input.as_deref(),
+                part: Some(p.part),
+                status: RowStatus::Part(p.status),
+                answer: p.answer.as_deref(),
+                error: p.error.clone(),
+                nanos: Some(p.nanos),
+                samples: Some(p.samples),
+                stats: p.stats.as_ref(),
+                memory: p.memory,
+            });
+
+            let run = (result.status != RunStatus::Completed).then(|| Row {
+                year,
+                day: result.day,
+                input: result.input.as_deref(),
+                part: None,
+                status: RowStatus::Run(&result.status),
+                answer: None,
+                error: result.status.reason(),
+                nanos: None,
+                samples: None,
+                stats: None,
+                memory: None,
+            });
+
+            parts.chain(run)
+        })
+        .collect()
+}
+
+/// Render the results of one or more years in a format.
+pub fn render(format: Format, results: &[(Year, Vec<DayResults>)]) -> String {
+    let rows = rows(results);
+
+    match format {
+        Format::Json => render_json(&rows),
+        Format::Csv => render_csv(&rows),
+        Format::Markdown => render_markdown(&rows),
+        Format::Junit => render_junit(&rows),
+    }
+}
+
+fn render_json(rows: &[Row]) -> String {
+    let optional_string =
+        |value: Option<&str>| value.map_or(JsonValue::Null, |x| JsonValue::String(x.into()));
+    let optional_number = |value: Option<f64>| value.map_or(JsonValue::Null, JsonValue::Number);
+
+    let records = rows
+        .iter()
+        .map(|row| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+            map.insert(
+                "year".into(),
+                JsonValue::Number(f64::from(row.year.into_inner())),
+            );
+            map.insert(
+                "day".into(),
+                JsonValue::Number(f64::from(row.day.into_inner())),
+            );
+            map.insert("input".into(), optional_string(row.input));
+            map.insert("part".into(), optional_number(row.part.map(f64::from)));
+            map.insert(
+                "status".into(),
+                JsonValue::String(row.status.as_str().into()),
+            );
+            map.insert("answer".into(), optional_string(row.answer));
+            map.insert("error".into(), optional_string(row.error.as_deref()));
+            map.insert("nanos".into(), optional_number(row.nanos));
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(
+                "samples".into(),
+                optional_number(row.samples.map(|x| x as f64)),
+            );
+            map.insert(
+                "stats".into(),
+                row.stats.map_or(JsonValue::Null, JsonValue::from),
+            );
+            map.insert(
+                "memory".into(),
+                row.memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut json = JsonValue::Array(records).format().unwrap();
+    json.push('\n');
+    json
+}
+
+fn render_csv(rows: &[Row]) -> String {
+    fn escape(value: &str) -> String {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.into()
+        }
+    }
+
+    let number = |value: Option<f64>| value.map(|x| x.to_string()).unwrap_or_default();
+    let count = |value: Option<u64>| value.map(|x| x.to_string()).unwrap_or_default();
+
+    let mut lines = vec![
+        "year,day,input,part,status,answer,error,nanos,samples,min,median,mean,std_dev,p95,total_bytes,peak_bytes,allocations".into(),
+    ];
+
+    for row in rows {
+        lines.push(
+            [
+                row.year.to_string(),
+                row.day.to_string(),
+                escape(row.input.unwrap_or_default()),
+                row.part.map(|x| x.to_string()).unwrap_or_default(),
+                row.status.as_str().into(),
+                escape(row.answer.unwrap_or_default()),
+                escape(row.error.as_deref().unwrap_or_default()),
+                number(row.nanos),
+                row.samples.map(|x| x.to_string()).unwrap_or_default(),
+                number(row.stats.map(|s| s.min)),
+                number(row.stats.map(|s| s.median)),
+                number(row.stats.map(|s| s.mean)),
+                number(row.stats.map(|s| s.std_dev)),
+                number(row.stats.map(|s| s.p95)),
+                count(row.memory.map(|m| m.total_bytes)),
+                count(row.memory.map(|m| m.peak_bytes)),
+                count(row.memory.map(|m| m.allocations)),
+            ]
+            .join(","),
+        );
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn render_markdown(rows: &[Row]) -> String {
+    let escape = |value: &str| value.replace('|', "\\|");
+    let time =
+        |nanos: Option<f64>| nanos.map_or_else(|| "-".into(), |x| format!("`{}`", format_nanos(x)));
+
+    let mut lines = vec![
+        "| Year | Day | Input | Part | Status | Answer | Time | Median | Std. dev. | Samples |"
+            .into(),
+        "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for row in rows {
+        let answer = match (&row.error, row.answer) {
+            (Some(error), _) => escape(error),
+            (None, Some(answer)) => format!("`{}`", escape(answer)),
+            (None, None) => "-".into(),
+        };
+
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} | {answer} | {} | {} | {} | {} |",
+            row.year,
+            row.day.into_inner(),
+            escape(row.input.unwrap_or("-")),
+            row.label(),
+            row.status.as_str(),
+            time(row.nanos),
+            time(row.stats.map(|s| s.median)),
+            time(row.stats.map(|s| s.std_dev)),
+            row.samples.map_or_else(|| "-".into(), |x| x.to_string()),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Every part is a test case. Unsolved parts are skipped, parts that returned an error fail and
+/// days that timed out, panicked or crashed are errors.
+fn render_junit(rows: &[Row]) -> String {
+    fn escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+
+    #[derive(Default)]
+    struct Counts {
+        tests: usize,
+        failures: usize,
+        errors: usize,
+        skipped: usize,
+        seconds: f64,
+    }
+
+    impl Counts {
+        fn add(&mut self, row: &Row) {
+            self.tests += 1;
+            self.seconds += row.nanos.unwrap_or_default() / 1e9;
+
+            match row.status {
+                RowStatus::Run(_) => self.errors += 1,
+                RowStatus::Part(PartStatus::Failed) => self.failures += 1,
+                RowStatus::Part(PartStatus::Unsolved) => self.skipped += 1,
+                RowStatus::Part(PartStatus::Solved) => {}
+            }
+        }
+
+        fn attributes(&self) -> String {
+            format!(
+                "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\"",
+                self.tests, self.failures, self.errors, self.skipped, self.seconds
+            )
+        }
+    }
+
+    let mut years: Vec<Year> = rows.iter().map(|row| row.year).collect();
+    years.dedup();
+
+    let mut total = Counts::default();
+    let mut suites = vec![];
+
+    for year in years {
+        let mut counts = Counts::default();
+        let mut cases = vec![];
+
+        for row in rows.iter().filter(|row| row.year == year) {
+            counts.add(row);
+            total.add(row);
+
+            let class = match row.input {
+                Some(input) => format!("{year}.day{}.{}", row.day, escape(input)),
+                None => format!("{year}.day{}", row.day),
+            };
+
+            let body = match row.status {
+                RowStatus::Run(status) => format!(
+                    "<error type=\"{}\" message=\"{}\"/>",
+                    status.as_str(),
+                    escape(row.error.as_deref().unwrap_or(status.as_str()))
+                ),
+                RowStatus::Part(PartStatus::Failed) => format!(
+                    "<failure message=\"{}\"/>",
+                    escape(row.error.as_deref().unwrap_or_default())
+                ),
+                RowStatus::Part(PartStatus::Unsolved) => "<skipped message=\"not solved\"/>".into(),
+                RowStatus::Part(PartStatus::Solved) => row
+                    .answer
+                    .map(|answer| format!("<system-out>{}</system-out>", escape(answer)))
+                    .unwrap_or_default(),
+            };
+
+            cases.push(format!(
+                "    <testcase classname=\"{class}\" name=\"{}\" time=\"{:.6}\">{body}</testcase>",
+                row.label(),
+                row.nanos.unwrap_or_default() / 1e9,
+            ));
+        }
+
+        suites.push(format!(
+            "  <testsuite name=\"{year}\" {}>\n{}\n  </testsuite>",
+            counts.attributes(),
+            cases.join("\n")
+        ));
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"advent_of_code\" {}>\n{}\n</testsuites>\n",
+        total.attributes(),
+        suites.join("\n")
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Format, render};
+    use crate::template::protocol::{PartResult, PartStatus};
+    use crate::template::run_multi::{DayResults, RunStatus};
+    use crate::{day, year};
+    use std::str::FromStr;
+    use tinyjson::JsonValue;
+
+    fn part(part: u8, status: PartStatus, answer: Option<&str>, error: Option<&str>) -> PartResult {
+        PartResult {
+            part,
+            status,
+            answer: answer.map(Into::into),
+            error: error.map(Into::into),
+            nanos: 1500.0,
+            samples: 1,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    fn get_mock_results() -> Vec<(crate::template::Year, Vec<DayResults>)> {
+        vec![(
+            year!(2025),
+            vec![
+                DayResults {
+                    day: day!(1),
+                    input: None,
+                    parts: vec![
+                        part(1, PartStatus::Solved, Some("42"), None),
+                        part(2, PartStatus::Unsolved, None, None),
+                    ],
+                    status: RunStatus::Completed,
+                    usage: None,
+                },
+                DayResults {
+                    day: day!(2),
+                    input: Some("alice".into()),
+                    parts: vec![part(1, PartStatus::Failed, None, Some("bad, \"range\""))],
+                    status: RunStatus::Timeout("did not finish within 1.0s".into()),
+                    usage: None,
+                },
+            ],
+        )]
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(Format::Csv, &get_mock_results());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1], "2025,01,,1,solved,42,,1500,1,,,,,,,,");
+        assert_eq!(
+            lines[3],
+            "2025,02,alice,1,failed,,\"bad, \"\"range\"\"\",1500,1,,,,,,,,"
+        );
+        assert_eq!(
+            lines[4],
+            "2025,02,alice,,timeout,,did not finish within 1.0s,,,,,,,,,,"
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = JsonValue::from_str(&render(Format::Json, &get_mock_results())).unwrap();
+        let records: &Vec<JsonValue> = json.get().unwrap();
+
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0]["answer"], JsonValue::String("42".into()));
+        assert_eq!(records[3]["part"], JsonValue::Null);
+        assert_eq!(records[3]["status"], JsonValue::String("timeout".into()));
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = render(Format::Markdown, &get_mock_results());
+
+        assert!(
+            markdown.contains("| 2025 | 1 | - | Part 1 | solved | `42` | `1.5µs` | - | - | 1 |")
+        );
+        assert!(
+            markdown
+                .contains("| 2025 | 2 | alice | Run | timeout | did not finish within 1.0s | - |")
+        );
+    }
+
+    #[test]
+    fn renders_junit() {
+        let junit = render(Format::Junit, &get_mock_results());
+
+        assert!(junit.contains(
+            "<testsuite name=\"2025\" tests=\"4\" failures=\"1\" errors=\"1\" skipped=\"1\""
+        ));
+        assert!(junit.contains("<failure message=\"bad, &quot;range&quot;\"/>"));
+        assert!(junit.contains("classname=\"2025.day02.alice\" name=\"Run\""));
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(Format::from_str("md").unwrap(), Format::Markdown);
+        assert!(Format::from_str("xml").is_err());
+    }
+}
@@ -1,56 +1,616 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::protocol::{PartResult, PartStatus, part_label};
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{InputSource, RunOptions};
+use crate::template::stats::{ResourceUsage, format_bytes, format_nanos};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Year, data_dir, named_inputs};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Label of the default input when a day is run against several inputs.
+const DEFAULT_INPUT_LABEL: &str = "default";
 
-    let mut need_space = false;
+/// Exit code of a process whose main thread panicked.
+const PANIC_EXIT_CODE: i32 = 101;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+/// `SIGABRT`, the signal that stops a process which panicked with `panic = "abort"`.
+/// It has the same number on every unix.
+const ABORT_SIGNAL: i32 = 6;
+
+/// How the run of a day ended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunStatus {
+    /// The solution ran to completion.
+    Completed,
+    /// The solution exceeded a timeout and was stopped.
+    Timeout(String),
+    /// The solution panicked.
+    Panic,
+    /// The solution could not be built or run, or exited with a non-zero exit code.
+    Failed(String),
+}
+
+impl RunStatus {
+    /// How a child process ended: stopped after a timeout, exited with `code`, or terminated by
+    /// `signal`. A panic either exits with code 101 or, with `panic = "abort"`, aborts.
+    pub fn from_exit(timeout: Option<String>, code: Option<i32>, signal: Option<i32>) -> Self {
+        match (timeout, code, signal) {
+            (Some(reason), _, _) => RunStatus::Timeout(reason),
+            (None, Some(0), _) => RunStatus::Completed,
+            (None, Some(PANIC_EXIT_CODE), _) | (None, None, Some(ABORT_SIGNAL)) => RunStatus::Panic,
+            (None, Some(code), _) => RunStatus::Failed(format!("exit code {code}")),
+            (None, None, Some(signal)) => {
+                RunStatus::Failed(format!("terminated by signal {signal}"))
             }
-            need_space = true;
+            (None, None, None) => RunStatus::Failed("terminated by a signal".into()),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunStatus::Completed => "completed",
+            RunStatus::Timeout(_) => "timeout",
+            RunStatus::Panic => "panic",
+            RunStatus::Failed(_) => "failed",
+        }
+    }
+
+    /// Why a run was unsuccessful, e.g. `exit code 1`.
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            RunStatus::Completed | RunStatus::Panic => None,
+            RunStatus::Timeout(reason) | RunStatus::Failed(reason) => Some(reason.clone()),
+        }
+    }
+
+    /// Describe an unsuccessful run, e.g. `TIMEOUT (no part finished within 5.00s)`.
+    pub fn describe(&self) -> Option<String> {
+        if *self == RunStatus::Completed {
+            return None;
+        }
+
+        let status = self.as_str().to_uppercase();
+
+        Some(match self.reason() {
+            Some(reason) => format!("{status} ({reason})"),
+            None => status,
+        })
+    }
+}
+
+/// Results reported by the parts of a single day for a single input.
+#[derive(Clone, Debug)]
+pub struct DayResults {
+    pub day: Day,
+    /// Name of the input, `None` for the default input.
+    pub input: Option<String>,
+    pub parts: Vec<PartResult>,
+    /// Parts that finished before a timeout or crash are kept in `parts`.
+    pub status: RunStatus,
+    /// Peak RSS and CPU time of the process, for days that ran as a child process.
+    pub usage: Option<ResourceUsage>,
+}
+
+impl DayResults {
+    pub fn part(&self, part: u8) -> Option<&PartResult> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+}
+
+/// A line of output, either for stdout or for stderr.
+#[derive(Clone, Debug)]
+enum Line {
+    Out(String),
+    Err(String),
+}
+
+/// Where the output of a day goes. Days that run concurrently buffer their output, so that it can
+/// be printed in order once the day is done.
+#[derive(Clone, Debug, Default)]
+pub struct Console {
+    buffer: Option<Arc<Mutex<Vec<Line>>>>,
+}
+
+impl Console {
+    fn buffered() -> Self {
+        Self {
+            buffer: Some(Arc::default()),
+        }
+    }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    fn is_buffered(&self) -> bool {
+        self.buffer.is_some()
+    }
+
+    pub fn println(&self, line: impl Into<String>) {
+        match &self.buffer {
+            Some(buffer) => buffer.lock().unwrap().push(Line::Out(line.into())),
+            None => println!("{}", line.into()),
+        }
+    }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    pub fn eprintln(&self, line: impl Into<String>) {
+        match &self.buffer {
+            Some(buffer) => buffer.lock().unwrap().push(Line::Err(line.into())),
+            None => eprintln!("{}", line.into()),
+        }
+    }
+
+    /// Print and clear the buffered output.
+    fn flush(&self) {
+        let Some(buffer) = &self.buffer else {
+            return;
+        };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        for line in buffer.lock().unwrap().drain(..) {
+            match line {
+                Line::Out(line) => println!("{line}"),
+                Line::Err(line) => eprintln!("{line}"),
             }
-        });
+        }
+    }
+}
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+/// Collect the timings of a set of day results.
+pub fn to_timings(results: &[DayResults]) -> Timings {
+    Timings {
+        data: results
+            .iter()
+            .filter(|r| !r.parts.is_empty())
+            .map(|r| Timing {
+                usage: r.usage,
+                ..Timing::from_results(r.day, r.input.as_deref(), &r.parts)
+            })
+            .collect(),
+        history: vec![],
+    }
+}
+
+/// Run a set of days of a year. Days compiled into the runner binary are executed in-process if
+/// the runner was built with the requested profile, all other days are run as child processes.
+/// Unless the days are timed, up to `jobs` days run concurrently. Their output is printed in order.
+/// Days that were not solved are not part of the returned results.
+pub fn run_multi(
+    solutions: &[&Solution],
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    options: &RunOptions,
+    jobs: usize,
+) -> Vec<DayResults> {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let run = |i: usize, console: &Console| {
+        if i > 0 {
+            console.println("");
+        }
+
+        let day = days[i];
+        console.println(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+        console.println("------");
+
+        let results = run_day(solutions, year, day, is_release, options, console);
+
+        if results.is_empty() {
+            console.println("Not solved.");
+        }
+
+        results
+    };
+
+    // timed days run one after another, so that they do not interfere with each other.
+    let day_results: Vec<DayResults> = if jobs > 1 && !options.is_timed {
+        run_concurrently(days.len(), jobs, run)
+    } else {
+        (0..days.len())
+            .flat_map(|i| run(i, &Console::default()))
+            .collect()
+    };
+
+    if options.is_timed {
+        let total_millis = day_results
+            .iter()
+            .flat_map(|r| &r.parts)
+            .filter(|p| p.status == PartStatus::Solved)
+            .map(|p| p.nanos)
+            .sum::<f64>()
+            / 1_000_000_f64;
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+    }
+
+    print_resources(&day_results);
+    print_failures(&day_results);
+
+    day_results
+}
+
+/// Run `count` tasks on up to `jobs` threads. The buffered output of each task is printed in order,
+/// as soon as all tasks before it are done.
+fn run_concurrently(
+    count: usize,
+    jobs: usize,
+    run: impl Fn(usize, &Console) -> Vec<DayResults> + Sync,
+) -> Vec<DayResults> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(count) {
+            let (next, run, sender) = (&next, &run, sender.clone());
+
+            scope.spawn(move || {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= count {
+                        break;
+                    }
+
+                    let console = Console::buffered();
+                    let results = run(i, &console);
+                    sender.send((i, console, results)).unwrap();
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut printed = 0;
+        let mut day_results = vec![];
+
+        for (i, console, results) in receiver {
+            finished.insert(i, (console, results));
+
+            while let Some((console, results)) = finished.remove(&printed) {
+                console.flush();
+                day_results.extend(results);
+                printed += 1;
+            }
+        }
+
+        day_results
+    })
+}
+
+/// List the peak RSS and CPU time of the days that ran as a child process, if any.
+fn print_resources(results: &[DayResults]) {
+    let rows: Vec<[String; 5]> = results
+        .iter()
+        .filter_map(|result| {
+            let usage = result.usage?;
+            let day = match &result.input {
+                Some(name) => format!("Day {} ({name})", result.day),
+                None => format!("Day {}", result.day),
+            };
+
+            Some([
+                day,
+                format_bytes(usage.peak_rss_bytes),
+                format_nanos(usage.user_nanos),
+                format_nanos(usage.system_nanos),
+                format!("{:.0}%", usage.cpu_percent()),
+            ])
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return;
+    }
+
+    let header = ["Day", "Max RSS", "User", "Sys", "CPU"].map(String::from);
+    let width = rows
+        .iter()
+        .chain([&header])
+        .map(|row| row[0].chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let format_row = |row: &[String; 5]| {
+        format!(
+            "{:<width$}  {:>10}  {:>10}  {:>10}  {:>5}",
+            row[0], row[1], row[2], row[3], row[4]
+        )
+    };
+
+    println!("\n{ANSI_BOLD}{}{ANSI_RESET}", format_row(&header));
+    rows.iter().for_each(|row| println!("{}", format_row(row)));
+}
+
+/// List the days that timed out, panicked or failed and the parts that returned an error, if any.
+fn print_failures(results: &[DayResults]) {
+    let failures: Vec<String> = results
+        .iter()
+        .flat_map(|result| {
+            let input = result
+                .input
+                .as_deref()
+                .map(|name| format!(" ({name})"))
+                .unwrap_or_default();
+
+            let day = result
+                .status
+                .describe()
+                .map(|status| format!("Day {}{input}: {status}", result.day));
+
+            let parts = result
+                .parts
+                .iter()
+                .filter(|p| p.status == PartStatus::Failed)
+                .map(move |p| {
+                    format!(
+                        "Day {}{input} {}: FAILED ({})",
+                        result.day,
+                        part_label(p.part),
+                        p.error.as_deref().unwrap_or_default()
+                    )
+                });
+
+            day.into_iter().chain(parts)
+        })
+        .collect();
+
+    if failures.is_empty() {
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Failures ({}){ANSI_RESET}", failures.len());
+    failures.iter().for_each(|failure| println!("⚠ {failure}"));
+}
+
+/// Run a day against each of its inputs. If the day has named inputs, every input is run and
+/// a table comparing the answers and times of the inputs is printed.
+/// Inputs that were not solved are not part of the returned results.
+pub fn run_day(
+    solutions: &[&Solution],
+    year: Year,
+    day: Day,
+    is_release: bool,
+    options: &RunOptions,
+    console: &Console,
+) -> Vec<DayResults> {
+    let inputs = inputs_to_run(year, day, &options.input);
+    let measure_memory = options.memory;
+    let mut day_results = vec![];
+
+    for (i, (name, input)) in inputs.iter().enumerate() {
+        if inputs.len() > 1 {
+            if i > 0 {
+                console.println("");
+            }
+            console.println(format!(
+                "{ANSI_ITALIC}{}{ANSI_RESET}",
+                name.as_deref().unwrap_or(DEFAULT_INPUT_LABEL)
+            ));
+        }
+
+        let options = RunOptions {
+            input: input.clone(),
+            memory: false,
+            ..options.clone()
+        };
+
+        // days can only be stopped after a timeout, their output can only be buffered and their
+        // resource usage can only be measured, if they run in a child process. days compiled into
+        // the runner only run in-process if the runner was built with the requested profile.
+        let runner_matches_profile = is_release != cfg!(debug_assertions);
+        let solution = registry::find(solutions, year, day).filter(|_| {
+            runner_matches_profile
+                && !options.timeouts.is_set()
+                && !options.resources
+                && !console.is_buffered()
+        });
+
+        let (mut parts, status, usage) = match solution {
+            Some(solution) => {
+                // parts that finished before a panic are kept, like the records of a child.
+                let mut parts = vec![];
+                let status = match panic::catch_unwind(AssertUnwindSafe(|| {
+                    solution.run(&options, &mut parts);
+                })) {
+                    Ok(()) => RunStatus::Completed,
+                    Err(_) => RunStatus::Panic,
+                };
+                (parts, status, None)
+            }
+            // a day that can not be run fails on its own, the other days keep running.
+            None => child_commands::run_solution(year, day, &options, is_release, console)
+                .unwrap_or_else(|e| (vec![], RunStatus::Failed(e.to_string()), None)),
+        };
+
+        if let Some(status) = status.describe() {
+            console.println(format!("⚠ {status}"));
+        }
+
+        if measure_memory && status == RunStatus::Completed && !parts.is_empty() {
+            add_memory(year, day, &options, is_release, console, &mut parts);
+        }
+
+        if !parts.is_empty() || status != RunStatus::Completed {
+            day_results.push(DayResults {
+                day,
+                input: name.clone(),
+                parts,
+                status,
+                usage,
+            });
+        }
+    }
+
+    if inputs.len() > 1 && !day_results.is_empty() {
+        print_input_table(&day_results, console);
+    }
+
+    day_results
+}
+
+/// Run a day once more with the heap profiler and add the heap usage to its parts.
+/// This is a separate run, because profiling the heap distorts runtimes.
+fn add_memory(
+    year: Year,
+    day: Day,
+    options: &RunOptions,
+    is_release: bool,
+    console: &Console,
+    parts: &mut [PartResult],
+) {
+    let options = RunOptions {
+        is_timed: false,
+        memory: true,
+        ..options.clone()
+    };
+
+    let (profiled, status, _) =
+        child_commands::run_solution(year, day, &options, is_release, console)
+            .unwrap_or_else(|e| (vec![], RunStatus::Failed(e.to_string()), None));
+
+    if let Some(status) = status.describe() {
+        console.println(format!("⚠ could not measure memory: {status}"));
+    }
+
+    for part in parts {
+        part.memory = profiled
+            .iter()
+            .find(|p| p.part == part.part)
+            .and_then(|p| p.memory);
+    }
+}
+
+/// The inputs a day is run against: an explicitly selected source, or the default input followed
+/// by the named inputs of the day.
+fn inputs_to_run(
+    year: Year,
+    day: Day,
+    selected: &InputSource,
+) -> Vec<(Option<String>, InputSource)> {
+    if *selected != InputSource::Inputs {
+        let name = match selected {
+            InputSource::Named(name) => Some(name.clone()),
+            _ => None,
+        };
+        return vec![(name, selected.clone())];
+    }
+
+    let names = named_inputs(year, day);
+
+    let has_default = data_dir(year)
+        .join("inputs")
+        .join(format!("{day}.txt"))
+        .exists();
+
+    if names.is_empty() || has_default {
+        let mut inputs = vec![(None, InputSource::Inputs)];
+        inputs.extend(
+            names
+                .into_iter()
+                .map(|name| (Some(name.clone()), InputSource::Named(name))),
+        );
+        inputs
     } else {
-        None
+        names
+            .into_iter()
+            .map(|name| (Some(name.clone()), InputSource::Named(name)))
+            .collect()
     }
 }
 
-#[allow(dead_code)]
+/// Print the answers and times of a day side by side, one row per input.
+pub fn print_input_table(results: &[DayResults], console: &Console) {
+    let cell = |result: &DayResults, part: u8| match result.part(part) {
+        Some(p) if p.status == PartStatus::Failed => "⚠".into(),
+        Some(p) => p.answer.clone().unwrap_or_else(|| "-".into()),
+        None => "-".into(),
+    };
+
+    let rows: Vec<[String; 4]> = results
+        .iter()
+        .map(|result| {
+            let nanos = result
+                .parts
+                .iter()
+                .filter(|p| p.status == PartStatus::Solved)
+                .map(|p| p.nanos)
+                .sum::<f64>();
+
+            [
+                result
+                    .input
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_INPUT_LABEL.into()),
+                cell(result, 1),
+                cell(result, 2),
+                format_nanos(nanos),
+            ]
+        })
+        .collect();
+
+    let header = ["Input", "Part 1", "Part 2", "Time"].map(String::from);
+    let widths: Vec<usize> = (0..4)
+        .map(|i| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_row = |row: &[String; 4]| {
+        format!(
+            "{:<w0$}  {:>w1$}  {:>w2$}  {:>w3$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        )
+    };
+
+    console.println("");
+    console.println(format!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(&header)));
+    rows.iter().for_each(|row| console.println(format_row(row)));
+}
+
+/// Print a header above the days of a year when running several years.
+pub fn print_year_header(year: Year) {
+    println!("{ANSI_BOLD}===== {year} ====={ANSI_RESET}\n");
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Protocol(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Protocol(e) => write!(f, "invalid result records: {e}"),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -60,198 +620,461 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{year}-{day}.rs")
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their results.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{Console, Error, RunStatus, get_path_for_bin};
+    use crate::template::protocol::{self, PartResult};
+    use crate::template::runner::{InputSource, RunOptions, Timeouts};
+    use crate::template::stats::{ResourceUsage, format_nanos};
+    use crate::template::{Day, Year};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        env, fs,
+        io::{self, BufRead, BufReader},
+        path::{Path, PathBuf},
+        process::{self, Child, Command, ExitStatus, Stdio},
+        str::FromStr,
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// How often a child with a timeout is checked for progress.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Run the solution bin for a given day and collect the results it reports.
+    /// Results of parts that finished before the solution crashed or timed out are kept.
+    /// The resource usage is only reported on platforms that support `wait4`.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        options: &RunOptions,
+        is_release: bool,
+        console: &Console,
+    ) -> Result<(Vec<PartResult>, RunStatus, Option<ResourceUsage>), Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return Ok((vec![], RunStatus::Completed, None));
+        }
+
+        // days without an input are not solved rather than failed, just like days run in-process.
+        if options.input != InputSource::Stdin
+            && let Err(e) = options.input.read(year, day)
+        {
+            console.eprintln(format!("could not open input file: {e}"));
+            return Ok((vec![], RunStatus::Completed, None));
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut build_args: Vec<String> =
+            vec!["--quiet".into(), "--bin".into(), format!("{year}-{day}")];
 
         if is_release {
-            args.push("--release");
+            build_args.push("--release".into());
+        }
+
+        if options.memory {
+            build_args.extend(["--features".into(), "dhat-heap".into()]);
+        }
+
+        // build up front and run the executable directly, so that neither compiling nor cargo
+        // count towards the timeouts and the resource usage.
+        let executable = match build(&build_args, console)? {
+            Ok(executable) => executable,
+            Err(status) => return Ok((vec![], status, None)),
+        };
+
+        let mut args: Vec<String> = vec![];
+
+        if options.is_timed {
+            // mirror `--time` flag and bench options to child invocations.
+            args.push("--time".into());
+            args.extend(options.bench.to_args());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if options.memory {
+            args.push("--memory".into());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        args.extend(options.input.to_args());
 
-        let mut cmd = Command::new("cargo")
+        // the child appends one record per part to this file.
+        let result_path = env::temp_dir().join(format!("aoc-{}-{year}-{day}.jsonl", process::id()));
+        let _ = fs::remove_file(&result_path);
+
+        let mut command = Command::new(executable);
+        command
             .args(&args)
+            .env(protocol::RESULT_FILE_ENV, &result_path)
+            .env("AOC_YEAR", year.to_string())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+
+        // running the solution in its own process group allows stopping it together with any
+        // process it started.
+        #[cfg(unix)]
+        if options.timeouts.is_set() {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+
+        // spawn child command with piped stdout/stderr and forward output to stdout/stderr.
+        let started = Instant::now();
+        let mut cmd = command.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stderr_console = console.clone();
+        let stderr_thread = thread::spawn(move || {
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                stderr_console.eprintln(line);
+            });
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        let stdout_console = console.clone();
+        let stdout_thread = thread::spawn(move || {
+            stdout.lines().map_while(Result::ok).for_each(|line| {
+                stdout_console.println(line);
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let (exit, timeout) = if options.timeouts.is_set() {
+            wait_with_timeouts(&mut cmd, &options.timeouts, &result_path)?
+        } else {
+            (wait(&mut cmd)?, None)
+        };
+
+        let wall_nanos = started.elapsed().as_secs_f64() * 1e9;
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
+
+        let results = protocol::read_results(&result_path).map_err(super::Error::Protocol);
+        let _ = fs::remove_file(&result_path);
+
+        let status = RunStatus::from_exit(timeout, exit.status.code(), signal(exit.status));
+
+        let usage = exit.usage.map(|usage| ResourceUsage {
+            wall_nanos,
+            ..usage
+        });
 
-        Ok(output)
+        Ok((results?, status, usage))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Build a solution bin with `cargo build` and return the path of its executable, or the
+    /// status of the failed build.
+    fn build(args: &[String], console: &Console) -> Result<Result<PathBuf, RunStatus>, Error> {
+        let output = Command::new("cargo")
+            .arg("build")
+            .args(args)
+            .arg("--message-format=json-render-diagnostics")
+            .stderr(Stdio::piped())
+            .output()?;
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        String::from_utf8_lossy(&output.stderr)
+            .lines()
+            .for_each(|line| console.eprintln(line));
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        if !output.status.success() {
+            return Ok(Err(RunStatus::from_exit(None, output.status.code(), None)));
+        }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+        // cargo reports every compiled artifact as a JSON message, the bin is the only executable.
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| JsonValue::from_str(line).ok())
+            .find_map(|message| {
+                let message = message.get::<HashMap<String, JsonValue>>()?;
+                message
+                    .get("executable")
+                    .and_then(|executable| executable.get::<String>())
+                    .map(PathBuf::from)
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            .map(Ok)
+            .ok_or_else(|| Error::Protocol("cargo did not report the executable.".into()))
+    }
+
+    /// Signal that terminated a process, if it was terminated by one.
+    #[cfg(unix)]
+    fn signal(status: ExitStatus) -> Option<i32> {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    }
+
+    #[cfg(not(unix))]
+    fn signal(_status: ExitStatus) -> Option<i32> {
+        None
+    }
+
+    /// Exit status of a child, together with the resources it used.
+    struct Exit {
+        status: ExitStatus,
+        usage: Option<ResourceUsage>,
+    }
+
+    /// Wait for a child to exit, stopping it once a timeout is exceeded.
+    /// A part counts as finished once its record shows up in the result file.
+    /// Also returns why the child was stopped, if it was.
+    fn wait_with_timeouts(
+        cmd: &mut Child,
+        timeouts: &Timeouts,
+        result_path: &Path,
+    ) -> Result<(Exit, Option<String>), Error> {
+        let started = Instant::now();
+        let mut part_started = started;
+        let mut finished_parts = 0;
+
+        loop {
+            if let Some(exit) = try_wait(cmd)? {
+                return Ok((exit, None));
+            }
+
+            let records = count_records(result_path);
+            if records > finished_parts {
+                finished_parts = records;
+                part_started = Instant::now();
+            }
+
+            let reason = match (timeouts.day, timeouts.part) {
+                (Some(limit), _) if started.elapsed() > limit => {
+                    Some(format!("did not finish within {}", format_limit(limit)))
+                }
+                (_, Some(limit)) if part_started.elapsed() > limit => {
+                    Some(format!("no part finished within {}", format_limit(limit)))
                 }
+                _ => None,
+            };
 
-                timings.total_nanos += nanos;
-            });
+            if let Some(reason) = reason {
+                kill(cmd)?;
+                return Ok((wait(cmd)?, Some(reason)));
+            }
 
-        timings
+            thread::sleep(POLL_INTERVAL);
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    fn count_records(result_path: &Path) -> usize {
+        fs::read(result_path)
+            .map(|content| content.iter().filter(|b| **b == b'\n').count())
+            .unwrap_or_default()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
+    fn format_limit(limit: Duration) -> String {
+        format_nanos(limit.as_secs_f64() * 1e9)
+    }
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+    /// Stop the child together with the processes it started.
+    fn kill(cmd: &mut Child) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            #[allow(clippy::cast_possible_wrap)]
+            let group = -(cmd.id() as i32);
+            // SAFETY: `kill` has no memory-safety preconditions.
+            unsafe { libc::kill(group, libc::SIGKILL) };
+            Ok(())
+        }
 
-        Some((str_timing, parsed_timing))
+        #[cfg(not(unix))]
+        cmd.kill()
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    fn wait(cmd: &mut Child) -> io::Result<Exit> {
+        loop {
+            if let Some(exit) = wait_child(cmd, true)? {
+                return Ok(exit);
+            }
+        }
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
+    fn try_wait(cmd: &mut Child) -> io::Result<Option<Exit>> {
+        wait_child(cmd, false)
+    }
 
-        use crate::day;
+    /// Reap the child with `wait4`, which also reports its peak RSS and CPU time.
+    /// `Child::wait` must not be called on a child that was reaped here.
+    #[cfg(unix)]
+    fn wait_child(cmd: &mut Child, block: bool) -> io::Result<Option<Exit>> {
+        use std::os::unix::process::ExitStatusExt;
 
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+        #[allow(clippy::cast_possible_wrap)]
+        let pid = cmd.id() as libc::pid_t;
+        let options = if block { 0 } else { libc::WNOHANG };
+        let mut status = 0;
+        // SAFETY: `rusage` only consists of integers, all zeroes is a valid value.
+        let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+
+        // SAFETY: `status` and `rusage` are valid for writes.
+        match unsafe { libc::wait4(pid, &raw mut status, options, &raw mut rusage) } {
+            0 => Ok(None),
+            -1 => match io::Error::last_os_error() {
+                e if e.kind() == io::ErrorKind::Interrupted => Ok(None),
+                e => Err(e),
+            },
+            _ => Ok(Some(Exit {
+                status: ExitStatus::from_raw(status),
+                usage: Some(resource_usage(&rusage)),
+            })),
         }
+    }
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+    #[cfg(not(unix))]
+    fn wait_child(cmd: &mut Child, block: bool) -> io::Result<Option<Exit>> {
+        let status = if block {
+            Some(cmd.wait()?)
+        } else {
+            cmd.try_wait()?
+        };
+
+        Ok(status.map(|status| Exit {
+            status,
+            usage: None,
+        }))
+    }
+
+    #[cfg(unix)]
+    #[allow(clippy::cast_precision_loss)]
+    fn resource_usage(rusage: &libc::rusage) -> ResourceUsage {
+        let nanos = |time: libc::timeval| time.tv_sec as f64 * 1e9 + time.tv_usec as f64 * 1e3;
+        // macOS reports the max RSS in bytes, other platforms in kilobytes.
+        let rss_unit = if cfg!(target_os = "macos") { 1 } else { 1024 };
+
+        ResourceUsage {
+            peak_rss_bytes: u64::try_from(rusage.ru_maxrss).unwrap_or_default() * rss_unit,
+            user_nanos: nanos(rusage.ru_utime),
+            system_nanos: nanos(rusage.ru_stime),
+            wall_nanos: 0.0,
         }
+    }
+
+    #[cfg(all(feature = "test_lib", unix))]
+    mod tests {
+        use super::wait_with_timeouts;
+        use crate::template::runner::Timeouts;
+        use std::{
+            env,
+            os::unix::process::CommandExt,
+            process::Command,
+            time::{Duration, Instant},
+        };
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+        fn stops_child_after_timeout() {
+            let mut cmd = Command::new("sh")
+                .args(["-c", "sleep 5"])
+                .process_group(0)
+                .spawn()
+                .unwrap();
+
+            let timeouts = Timeouts {
+                day: Some(Duration::from_millis(50)),
+                part: None,
+            };
+            let result_path = env::temp_dir().join("aoc-stops-child-after-timeout.jsonl");
+
+            let started = Instant::now();
+            let (exit, reason) = wait_with_timeouts(&mut cmd, &timeouts, &result_path).unwrap();
+
+            assert!(started.elapsed() < Duration::from_secs(2));
+            assert_eq!(reason.as_deref(), Some("did not finish within 50.0ms"));
+            assert_eq!(exit.status.code(), None);
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Console, DayResults, RunStatus, run_concurrently, run_day};
+    use crate::template::Day;
+    use crate::template::registry::Solution;
+    use crate::template::runner::{InputSource, RunOptions, run_part};
+    use crate::{day, year};
+    use std::{env, fs, process, thread, time::Duration};
+
+    #[test]
+    fn maps_exits_to_run_status() {
+        assert_eq!(
+            RunStatus::from_exit(None, Some(0), None),
+            RunStatus::Completed
+        );
+        assert_eq!(
+            RunStatus::from_exit(Some("did not finish within 1.0s".into()), None, Some(9)),
+            RunStatus::Timeout("did not finish within 1.0s".into())
+        );
+        assert_eq!(
+            RunStatus::from_exit(None, Some(101), None),
+            RunStatus::Panic
+        );
+        assert_eq!(RunStatus::from_exit(None, None, Some(6)), RunStatus::Panic);
+        assert_eq!(
+            RunStatus::from_exit(None, Some(1), None),
+            RunStatus::Failed("exit code 1".into())
+        );
+        assert_eq!(
+            RunStatus::from_exit(None, None, Some(9)),
+            RunStatus::Failed("terminated by signal 9".into())
+        );
+    }
+
+    #[test]
+    fn keeps_parts_finished_before_a_panic() {
+        static SOLUTION: Solution = Solution {
+            year: year!(2025),
+            day: day!(1),
+            solve: |input, options, results| {
+                let part_one = |input: &str| input.trim().parse::<u64>().ok();
+                results.push(run_part(part_one, input, year!(2025), day!(1), 1, options));
+                panic!("part two panicked");
+            },
+        };
+
+        let path = env::temp_dir().join(format!("aoc-{}-panic.txt", process::id()));
+        fs::write(&path, "42\n").unwrap();
+
+        let options = RunOptions {
+            input: InputSource::File(path.clone()),
+            ..RunOptions::default()
+        };
+        let is_release = !cfg!(debug_assertions);
+        let results = run_day(
+            &[&SOLUTION],
+            year!(2025),
+            day!(1),
+            is_release,
+            &options,
+            &Console::default(),
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, RunStatus::Panic);
+        assert_eq!(results[0].parts.len(), 1);
+        assert_eq!(results[0].parts[0].answer.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn keeps_order_of_concurrent_days() {
+        let results = run_concurrently(5, 3, |i, console| {
+            // later days finish first.
+            thread::sleep(Duration::from_millis(10 * (5 - i as u64)));
+            console.println(format!("day {i}"));
+
+            vec![DayResults {
+                day: Day::new(i as u8 + 1).unwrap(),
+                input: None,
+                parts: vec![],
+                status: RunStatus::Completed,
+                usage: None,
+            }]
+        });
+
+        let days: Vec<u8> = results.iter().map(|r| r.day.into_inner()).collect();
+        assert_eq!(days, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -1,100 +1,665 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
-use std::process::Output;
+use std::io::{self, Read, Write, stdin, stdout};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, fs, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::{AnswerCheck, Answers};
+use crate::template::protocol::{self, PARSE_PART, PartResult, PartStatus, part_label};
+use crate::template::stats::{MemoryStats, Stats, format_nanos};
+use crate::template::submissions::{self, Submissions};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, Year, aoc_client, data_dir, try_read_file};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Relative half-width of the 95% confidence interval of the mean at which benching stops early.
+const TARGET_PRECISION: f64 = 0.01;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Options that control how solution parts are run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench every part instead of running it once.
+    pub is_timed: bool,
+    /// Submit the result of this part.
+    pub submit: Option<u8>,
+    /// Store the answers as known correct answers.
+    pub record: bool,
+    /// Submit even if the submission journal says the answer is wrong.
+    pub force: bool,
+    /// How parts are sampled when benching.
+    pub bench: BenchOptions,
+    /// Where the input of the day is read from.
+    pub input: InputSource,
+    /// Wall-clock limits after which a day is stopped.
+    pub timeouts: Timeouts,
+    /// Measure the heap usage of every part. Requires the `dhat-heap` feature.
+    pub memory: bool,
+    /// Run every day in its own process and record its peak RSS and CPU time.
+    pub resources: bool,
+}
+
+impl RunOptions {
+    /// Parse the arguments that `solve` and `run_multi` pass to solution binaries.
+    pub fn from_args() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+
+        let Ok(submit) = args.opt_value_from_str("--submit") else {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+            process::exit(1);
+        };
+
+        let bench = match BenchOptions::from_args(&mut args) {
+            Ok(bench) => bench,
+            Err(e) => {
+                eprintln!("Unexpected command-line input: {e}");
+                process::exit(1);
+            }
+        };
+
+        let input = match InputSource::from_args(&mut args) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Unexpected command-line input: {e}");
+                process::exit(1);
+            }
+        };
+
+        Self {
+            is_timed: args.contains("--time"),
+            submit,
+            record: args.contains("--record"),
+            force: args.contains("--force"),
+            bench,
+            input,
+            timeouts: Timeouts::default(),
+            memory: args.contains("--memory"),
+            resources: false,
+        }
+    }
+}
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The personal puzzle input, e.g. `data/2025/inputs/01.txt`.
+    #[default]
+    Inputs,
+    /// One of several named inputs of a day, e.g. `data/2025/inputs/01/alice.txt`.
+    Named(String),
+    /// An example of the puzzle. `1` is `data/2025/examples/01.txt`, `2` is `01-2.txt` and so on.
+    Example(u8),
+    /// An arbitrary file.
+    File(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parse `--input <path>` (`-` for stdin), `--input-name <name>` or `--example <n>`.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let input: Option<PathBuf> = args.opt_value_from_str("--input")?;
+        let name: Option<String> = args.opt_value_from_str("--input-name")?;
+        let example: Option<u8> = args.opt_value_from_str("--example")?;
+
+        Ok(match (input, name, example) {
+            (Some(path), None, None) if path.as_os_str() == "-" => Self::Stdin,
+            (Some(path), None, None) => Self::File(path),
+            (None, Some(name), None) => Self::Named(name),
+            (None, None, Some(0)) => {
+                return Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: "examples are numbered starting from 1".into(),
+                });
+            }
+            (None, None, Some(n)) => Self::Example(n),
+            (None, None, None) => Self::Inputs,
+            _ => {
+                return Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: "--input, --input-name and --example can not be combined".into(),
+                });
+            }
+        })
+    }
+
+    /// Arguments that reproduce this source in a child solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Inputs => vec![],
+            Self::Named(name) => vec!["--input-name".into(), name.clone()],
+            Self::Example(n) => vec!["--example".into(), n.to_string()],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Read the input of a day from this source.
+    pub fn read(&self, year: Year, day: Day) -> Result<String, io::Error> {
+        match self {
+            Self::Inputs => try_read_file("inputs", year, day),
+            Self::Named(name) => fs::read_to_string(
+                data_dir(year)
+                    .join("inputs")
+                    .join(day.to_string())
+                    .join(format!("{name}.txt")),
+            ),
+            Self::Example(1) => try_read_file("examples", year, day),
+            Self::Example(n) => fs::read_to_string(
+                data_dir(year)
+                    .join("examples")
+                    .join(format!("{day}-{n}.txt")),
+            ),
+            Self::File(path) => fs::read_to_string(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Controls how long a part is sampled when benching.
+///
+/// Defaults can be overridden with the `AOC_BENCH_TIME`, `AOC_BENCH_MIN_SAMPLES`, `AOC_BENCH_MAX_SAMPLES`
+/// and `AOC_BENCH_WARMUP` environment variables, which in turn are overridden by command-line flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Time budget for sampling a part, warmup excluded.
+    pub budget: Duration,
+    /// Number of samples collected even if the budget is exhausted.
+    pub min_samples: u128,
+    /// Number of samples after which sampling stops regardless of precision.
+    pub max_samples: u128,
+    /// Iterations run before sampling starts. These are excluded from the statistics.
+    pub warmup: u128,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            warmup: 3,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Read options from the environment, falling back to defaults.
+    pub fn from_env() -> Self {
+        fn read<T>(key: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+            let value = env::var(key).ok()?;
+            parse(&value)
+                .map_err(|e| eprintln!("Ignoring invalid value for {key}: {e}"))
+                .ok()
+        }
+
+        let parse_count = |s: &str| s.parse::<u128>().map_err(|e| e.to_string());
+        let default = Self::default();
+
+        Self {
+            budget: read("AOC_BENCH_TIME", parse_duration).unwrap_or(default.budget),
+            min_samples: read("AOC_BENCH_MIN_SAMPLES", parse_count).unwrap_or(default.min_samples),
+            max_samples: read("AOC_BENCH_MAX_SAMPLES", parse_count).unwrap_or(default.max_samples),
+            warmup: read("AOC_BENCH_WARMUP", parse_count).unwrap_or(default.warmup),
+        }
+    }
+
+    /// Read options from the environment and override them with command-line flags.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let env = Self::from_env();
+
+        let options = Self {
+            budget: args
+                .opt_value_from_fn("--bench-time", parse_duration)?
+                .unwrap_or(env.budget),
+            min_samples: args
+                .opt_value_from_str("--min-samples")?
+                .unwrap_or(env.min_samples),
+            max_samples: args
+                .opt_value_from_str("--max-samples")?
+                .unwrap_or(env.max_samples),
+            warmup: args.opt_value_from_str("--warmup")?.unwrap_or(env.warmup),
+        };
+
+        if options.min_samples == 0 || options.min_samples > options.max_samples {
+            return Err(pico_args::Error::Utf8ArgumentParsingFailed {
+                value: format!(
+                    "--min-samples {} --max-samples {}",
+                    options.min_samples, options.max_samples
+                ),
+                cause: "expected 0 < min samples <= max samples".into(),
+            });
+        }
+
+        Ok(options)
+    }
+
+    /// Arguments that reproduce these options in a child solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--bench-time".into(),
+            format!("{}ns", self.budget.as_nanos()),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+            "--warmup".into(),
+            self.warmup.to_string(),
+        ]
+    }
+}
+
+/// Wall-clock limits for running a day. Days are stopped once a limit is exceeded, which requires
+/// running them as child processes rather than in-process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Limit for all parts of a day together.
+    pub day: Option<Duration>,
+    /// Limit for a single part, or the parse step.
+    pub part: Option<Duration>,
+}
+
+impl Timeouts {
+    /// Parse `--timeout <duration>` and `--part-timeout <duration>`.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            day: args.opt_value_from_fn("--timeout", parse_duration)?,
+            part: args.opt_value_from_fn("--part-timeout", parse_duration)?,
+        })
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.day.is_some() || self.part.is_some()
+    }
+}
+
+/// Parse a duration such as `2s`, `500ms`, `1.5s` or `250` (seconds).
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration `{s}`"))?;
+
+    let seconds = match unit.trim() {
+        "" | "s" => value,
+        "ms" => value / 1e3,
+        "us" | "µs" => value / 1e6,
+        "ns" => value / 1e9,
+        "m" | "min" => value * 60.0,
+        unit => return Err(format!("unknown unit `{unit}` in duration `{s}`")),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
+}
+
+/// Timing information collected for a solution part.
+struct Measurement {
+    duration: Duration,
+    samples: u128,
+    stats: Option<Stats>,
+    memory: Option<MemoryStats>,
+}
+
+/// Run the parse step of a solution that parses its input once and report it as [`PARSE_PART`].
+pub fn run_parse<T>(
+    func: impl Fn(&str) -> T,
+    input: &str,
+    options: &RunOptions,
+) -> (T, PartResult) {
+    let label = part_label(PARSE_PART);
+
+    let (parsed, measurement) = run_timed(func, input, options, |_| {
+        print!("{label}:");
+    });
+
+    print!("\r");
+    println!("{label}:{}", format_duration(&measurement));
+
+    let record = PartResult {
+        part: PARSE_PART,
+        status: PartStatus::Solved,
+        answer: None,
+        error: None,
+        #[allow(clippy::cast_precision_loss)]
+        nanos: measurement.duration.as_nanos() as f64,
+        samples: measurement.samples,
+        stats: measurement.stats,
+        memory: measurement.memory,
+    };
+
+    protocol::emit(&record);
+
+    (parsed, record)
+}
+
+/// Return types of solution parts.
+///
+/// Parts return `Option<T>`, where `None` means that the part is not implemented yet,
+/// or `Result<T, E>`, where the error is reported as a failure of the part.
+pub trait PartOutput {
+    type Answer: Display;
+
+    /// The answer of the part, `Ok(None)` if it is not implemented, or the error message.
+    fn outcome(&self) -> Result<Option<&Self::Answer>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn outcome(&self) -> Result<Option<&T>, String> {
+        Ok(self.as_ref())
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    type Answer = T;
+
+    fn outcome(&self) -> Result<Option<&T>, String> {
+        self.as_ref().map(Some).map_err(ToString::to_string)
+    }
+}
+
+pub fn run_part<I: Copy, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
+    let part_str = part_label(part);
+
+    let (result, measurement) = run_timed(func, input, options, |result| {
+        print_result(&result.outcome(), &part_str, "");
+    });
+
+    let outcome = result.outcome();
+    let answer = outcome
+        .as_ref()
+        .ok()
+        .and_then(|answer| answer.map(ToString::to_string));
+
+    // known answers only apply to the personal input.
+    let marker = answer
+        .as_deref()
+        .filter(|_| options.input == InputSource::Inputs)
+        .map(|answer| format_answer_check(year, day, part, answer))
+        .unwrap_or_default();
+
+    print_result(
+        &outcome,
+        &part_str,
+        &format!("{}{marker}", format_duration(&measurement)),
+    );
+
+    let record = PartResult {
+        part,
+        status: match &outcome {
+            Ok(Some(_)) => PartStatus::Solved,
+            Ok(None) => PartStatus::Unsolved,
+            Err(_) => PartStatus::Failed,
+        },
+        answer,
+        error: outcome.err(),
+        #[allow(clippy::cast_precision_loss)]
+        nanos: measurement.duration.as_nanos() as f64,
+        samples: measurement.samples,
+        stats: measurement.stats,
+        memory: measurement.memory,
+    };
+
+    protocol::emit(&record);
+
+    if let Some(answer) = &record.answer
+        && options.input == InputSource::Inputs
+    {
+        if options.record {
+            record_answer(year, day, part, answer);
+        }
+
+        if options.submit == Some(part) {
+            submit_answer(answer, year, day, part, options.force);
+        }
+    }
+
+    record
+}
+
+/// Marker shown next to an answer if the correct answer of a part is known.
+fn format_answer_check(year: Year, day: Day, part: u8, answer: &str) -> String {
+    let answers = Answers::read_from_file(year);
+
+    match answers.verify(day, part, answer) {
+        AnswerCheck::Correct => " ✔".into(),
+        AnswerCheck::Wrong => format!(" ✖ expected {}", answers.get(day, part).unwrap_or_default()),
+        AnswerCheck::Unknown => String::new(),
+    }
+}
+
+/// Submit an answer unless the submission journal knows it to be wrong, and journal the verdict.
+fn submit_answer(answer: &str, year: Year, day: Day, part: u8, force: bool) {
+    let mut submissions = Submissions::read_from_file(year);
+
+    if !force && let Err(reason) = submissions.check(day, part, answer) {
+        eprintln!("Not submitting: {reason}. Pass --force to submit anyway.");
+        return;
+    }
+
+    println!("Submitting result...");
+    let response = match aoc_client::submit(year, day, part, answer) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("failed to submit answer: {e}");
+            return;
+        }
+    };
+
+    let verdict = submissions.push(day, part, answer, &response).verdict;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Err(e) = submissions.store_file(year) {
+        eprintln!("Failed to store submission: {e}");
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if verdict == submissions::Verdict::Correct {
+        record_answer(year, day, part, answer);
+    }
+}
+
+fn record_answer(year: Year, day: Day, part: u8, answer: &str) {
+    match Answers::record(year, day, part, answer) {
+        Ok(()) => println!("Recorded answer for day {day} part {part}."),
+        Err(e) => eprintln!("Failed to record answer: {e}"),
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (see [`bench`]).
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Measurement) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = if options.memory {
+            // testing mode keeps the profiler from writing `dhat-heap.json`.
+            dhat::Profiler::builder().testing().build()
+        } else {
+            dhat::Profiler::new_heap()
+        };
+
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        let counter = crate::template::alloc::Counter::start();
+
+        let result = func(input);
 
-        func(input)
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        let memory = Some(counter.stop());
+
+        #[cfg(feature = "dhat-heap")]
+        let memory = options.memory.then(|| {
+            let stats = dhat::HeapStats::get();
+            MemoryStats {
+                total_bytes: stats.total_bytes,
+                peak_bytes: stats.max_bytes as u64,
+                allocations: stats.total_blocks,
+            }
+        });
+
+        #[cfg(not(any(feature = "dhat-heap", feature = "count-allocs")))]
+        let memory = None;
+
+        (result, memory)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let mut measurement = if options.is_timed {
+        bench(func, input, &options.bench)
     } else {
-        (base_time, 1)
+        Measurement {
+            duration: base_time,
+            samples: 1,
+            stats: None,
+            memory: None,
+        }
     };
 
-    (result, run.0, run.1)
+    measurement.memory = memory;
+
+    (result, measurement)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Bench a solution part.
+///  1. warmup iterations run first and are discarded. Warmup stops early once it used a tenth of the budget.
+///  2. samples are collected until the confidence interval of the mean is within [`TARGET_PRECISION`]
+///     or the budget is exhausted, bounded by the minimum and maximum sample counts.
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, options: &BenchOptions) -> Measurement {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let warmup_timer = Instant::now();
+    for _ in 0..options.warmup {
+        black_box(func(black_box(input)));
+        if warmup_timer.elapsed() > options.budget / 10 {
+            break;
+        }
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    let mut estimate = RunningMean::default();
+    let bench_timer = Instant::now();
 
-    for _ in 0..bench_iterations {
+    loop {
         let timer = Instant::now();
         black_box(func(black_box(input)));
-        timers.push(timer.elapsed());
+        let elapsed = timer.elapsed();
+
+        timers.push(elapsed);
+        estimate.push(elapsed);
+
+        let samples = timers.len() as u128;
+
+        if samples >= options.max_samples {
+            break;
+        }
+
+        if samples >= options.min_samples
+            && (estimate.is_precise() || bench_timer.elapsed() >= options.budget)
+        {
+            break;
+        }
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    let stats = Stats::from_samples(&timers);
+
+    Measurement {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        duration: Duration::from_nanos(stats.map_or(0.0, |s| s.mean) as u64),
+        samples: timers.len() as u128,
+        stats,
+        memory: None,
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Incrementally tracks mean and variance of samples (Welford's algorithm).
+#[derive(Default)]
+struct RunningMean {
+    count: f64,
+    mean: f64,
+    m2: f64,
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+impl RunningMean {
+    fn push(&mut self, sample: Duration) {
+        #[allow(clippy::cast_precision_loss)]
+        let x = sample.as_nanos() as f64;
+        self.count += 1.0;
+        let delta = x - self.mean;
+        self.mean += delta / self.count;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Whether the 95% confidence interval of the mean is narrower than [`TARGET_PRECISION`].
+    fn is_precise(&self) -> bool {
+        if self.count < 2.0 || self.mean <= 0.0 {
+            return false;
+        }
+
+        let std_err = (self.m2 / (self.count - 1.0)).sqrt() / self.count.sqrt();
+        1.96 * std_err / self.mean <= TARGET_PRECISION
+    }
+}
+
+fn format_duration(measurement: &Measurement) -> String {
+    let Measurement {
+        duration, samples, ..
+    } = measurement;
+
+    // runtimes are meaningless while dhat profiles the heap.
+    if cfg!(feature = "dhat-heap")
+        && let Some(memory) = measurement.memory
+    {
+        return format!(" ({memory})");
+    }
+
+    // allocations counted by the `count-allocs` allocator are shown next to the runtime.
+    let memory = measurement
+        .memory
+        .map(|memory| format!("; {memory}"))
+        .unwrap_or_default();
+
+    match measurement.stats {
+        Some(stats) if *samples > 1 => format!(
+            " ({duration:.1?} @ {samples} samples; min {}, median {}, p95 {}, σ {}{memory})",
+            format_nanos(stats.min),
+            format_nanos(stats.median),
+            format_nanos(stats.p95),
+            format_nanos(stats.std_dev),
+        ),
+        _ if *samples == 1 => format!(" ({duration:.1?}{memory})"),
+        _ => format!(" ({duration:.1?} @ {samples} samples{memory})"),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(outcome: &Result<Option<T>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
+    match outcome {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -114,7 +679,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,46 +687,114 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(error) => {
+            if is_intermediate_result {
+                print!("{part}: ⚠");
+            } else {
+                print!("\r");
+                println!("{part}: ⚠ {ANSI_ITALIC}error: {error}{ANSI_RESET}{duration_str}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputSource, RunningMean, Timeouts, parse_duration};
+    use std::{ffi::OsString, path::PathBuf, time::Duration};
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    fn parse_input(args: Vec<impl Into<OsString>>) -> Result<InputSource, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_vec(args.into_iter().map(Into::into).collect());
+        InputSource::from_args(&mut args)
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("2s").unwrap(), Duration::from_secs(2));
+        assert_eq!(parse_duration("1.5").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("250µs").unwrap(), Duration::from_micros(250));
+        assert_eq!(parse_duration("10ns").unwrap(), Duration::from_nanos(10));
+        assert_eq!(parse_duration("1min").unwrap(), Duration::from_secs(60));
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    #[test]
+    fn rejects_invalid_durations() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("3 parsecs").is_err());
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    #[test]
+    fn parses_timeouts() {
+        let mut args = pico_args::Arguments::from_vec(vec!["--part-timeout".into(), "2s".into()]);
+        let timeouts = Timeouts::from_args(&mut args).unwrap();
+        assert_eq!(timeouts.day, None);
+        assert_eq!(timeouts.part, Some(Duration::from_secs(2)));
+        assert!(timeouts.is_set());
 
-    if part_submit != part {
-        return None;
+        let mut args = pico_args::Arguments::from_vec(vec![]);
+        assert!(!Timeouts::from_args(&mut args).unwrap().is_set());
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(
+            parse_input(Vec::<&str>::new()).unwrap(),
+            InputSource::Inputs
+        );
+        assert_eq!(
+            parse_input(vec!["--input", "-"]).unwrap(),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            parse_input(vec!["--input", "edge.txt"]).unwrap(),
+            InputSource::File(PathBuf::from("edge.txt"))
+        );
+        assert_eq!(
+            parse_input(vec!["--example", "2"]).unwrap(),
+            InputSource::Example(2)
         );
-        process::exit(1);
+        assert!(parse_input(vec!["--example", "0"]).is_err());
+        assert_eq!(
+            parse_input(vec!["--input-name", "alice"]).unwrap(),
+            InputSource::Named("alice".into())
+        );
+        assert!(parse_input(vec!["--example", "1", "--input", "-"]).is_err());
+        assert!(parse_input(vec!["--input-name", "alice", "--input", "-"]).is_err());
+    }
+
+    #[test]
+    fn roundtrips_input_sources() {
+        for source in [
+            InputSource::Inputs,
+            InputSource::Named("alice".into()),
+            InputSource::Example(3),
+            InputSource::File(PathBuf::from("edge.txt")),
+            InputSource::Stdin,
+        ] {
+            assert_eq!(parse_input(source.to_args()).unwrap(), source);
+        }
+    }
+
+    #[test]
+    fn detects_precise_estimates() {
+        let mut estimate = RunningMean::default();
+        estimate.push(Duration::from_nanos(1000));
+        assert!(!estimate.is_precise());
+
+        for _ in 0..10 {
+            estimate.push(Duration::from_nanos(1000));
+        }
+        assert!(estimate.is_precise());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn detects_noisy_estimates() {
+        let mut estimate = RunningMean::default();
+        for nanos in [100, 10_000, 500, 20_000, 50] {
+            estimate.push(Duration::from_nanos(nanos));
+        }
+        assert!(!estimate.is_precise());
+    }
 }
@@ -0,0 +1,135 @@
+/// Solutions that parse their input once and share the parsed input between both parts.
+use crate::template::protocol::PartResult;
+use crate::template::runner::{PartOutput, RunOptions, run_parse, run_part};
+use crate::template::{Day, Year};
+
+/// An alternative to plain `part_one` / `part_two` functions for days whose parts work on the same
+/// parsed input. The input is parsed once, and the parse step is timed separately from the parts.
+///
+/// Register an implementation with [`solver!`](crate::solver):
+///
+/// ```ignore
+/// advent_of_code::solver!(1, Day01);
+///
+/// struct Day01;
+///
+/// impl advent_of_code::template::solver::Solver for Day01 {
+///     type Input = Vec<u64>;
+///     type Output1 = Option<u64>;
+///     type Output2 = Option<String>;
+///
+///     fn parse(input: &str) -> Self::Input {
+///         input.lines().map(|line| line.parse().unwrap()).collect()
+///     }
+///
+///     fn part_one(input: &Self::Input) -> Option<u64> {
+///         input.iter().max().copied()
+///     }
+///
+///     fn part_two(input: &Self::Input) -> Option<String> {
+///         Some(input.iter().map(u64::to_string).collect())
+///     }
+/// }
+/// ```
+pub trait Solver {
+    /// The parsed puzzle input, shared by both parts.
+    type Input;
+    /// What part one returns: `Option<T>`, or `Result<T, E>` for parts that can fail.
+    type Output1: PartOutput;
+    /// What part two returns, independent of part one.
+    type Output2: PartOutput;
+
+    fn parse(input: &str) -> Self::Input;
+
+    fn part_one(input: &Self::Input) -> Self::Output1;
+
+    fn part_two(input: &Self::Input) -> Self::Output2;
+}
+
+/// Parse the input once, then run both parts against the parsed input.
+/// The parse step is reported as part [`PARSE_PART`](crate::template::protocol::PARSE_PART).
+/// Results are pushed as soon as a step finishes.
+pub fn run<S: Solver>(
+    input: &str,
+    year: Year,
+    day: Day,
+    options: &RunOptions,
+    results: &mut Vec<PartResult>,
+) {
+    let (parsed, parse_result) = run_parse(S::parse, input, options);
+    results.push(parse_result);
+    results.push(run_part(S::part_one, &parsed, year, day, 1, options));
+    results.push(run_part(S::part_two, &parsed, year, day, 2, options));
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for a [`Solver`].
+///
+/// The first parameter is the day, the second the type implementing [`Solver`].
+#[macro_export]
+macro_rules! solver {
+    ($day:expr, $solver:ty) => {
+        $crate::solution!(@consts $day);
+
+        /// The parts of this day, used by the runner binary to run it in-process.
+        #[allow(dead_code)]
+        pub static SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                solve: |input, options, results| {
+                    $crate::template::solver::run::<$solver>(input, YEAR, DAY, options, results)
+                },
+            };
+
+        $crate::solution!(@main);
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Solver, run};
+    use crate::template::protocol::PartStatus;
+    use crate::template::runner::{InputSource, RunOptions};
+    use crate::{day, year};
+
+    struct Numbers;
+
+    impl Solver for Numbers {
+        type Input = Vec<u64>;
+        type Output1 = Option<u64>;
+        type Output2 = Result<u64, String>;
+
+        fn parse(input: &str) -> Self::Input {
+            input.lines().map(|line| line.parse().unwrap()).collect()
+        }
+
+        fn part_one(input: &Self::Input) -> Self::Output1 {
+            Some(input.iter().sum())
+        }
+
+        fn part_two(input: &Self::Input) -> Self::Output2 {
+            input
+                .iter()
+                .min()
+                .copied()
+                .filter(|min| *min > 1)
+                .ok_or("no minimum".into())
+        }
+    }
+
+    #[test]
+    fn reports_failed_parts() {
+        let options = RunOptions {
+            input: InputSource::Stdin,
+            ..RunOptions::default()
+        };
+
+        let mut results = vec![];
+        run::<Numbers>("1\n2\n", year!(2025), day!(1), &options, &mut results);
+
+        assert_eq!(results[1].status, PartStatus::Solved);
+        assert_eq!(results[1].answer.as_deref(), Some("3"));
+        assert_eq!(results[2].status, PartStatus::Failed);
+        assert_eq!(results[2].error.as_deref(), Some("no minimum"));
+    }
+}
@@ -0,0 +1,374 @@
+/// Summary statistics over the samples collected while benching a solution part.
+use std::{collections::HashMap, fmt::Display, time::Duration};
+
+use tinyjson::JsonValue;
+
+/// Statistics of a benchmark, all values in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub p95: f64,
+    /// Number of samples the statistics were computed from.
+    pub samples: u128,
+}
+
+impl Stats {
+    /// Compute statistics from a set of samples. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        // sample variance, like the running estimate the bench loop stops on.
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (len - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            min: nanos[0],
+            median: percentile(&nanos, 50.0),
+            mean,
+            std_dev: variance.sqrt(),
+            p95: percentile(&nanos, 95.0),
+            samples: samples.len() as u128,
+        })
+    }
+}
+
+/// Heap usage of a single run of a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// Largest number of bytes allocated at the same time.
+    pub peak_bytes: u64,
+    pub allocations: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} peak, {} total",
+            self.allocations,
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes)
+        )
+    }
+}
+
+/// Resources used by the process of a day, as reported by the operating system.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResourceUsage {
+    /// Peak resident set size.
+    pub peak_rss_bytes: u64,
+    /// CPU time spent in user mode, summed over all threads.
+    pub user_nanos: f64,
+    /// CPU time spent in the kernel, summed over all threads.
+    pub system_nanos: f64,
+    /// Wall-clock time from starting the process until it exited.
+    pub wall_nanos: f64,
+}
+
+impl ResourceUsage {
+    /// CPU time relative to the wall-clock time, in percent. Exceeds 100% if several threads ran
+    /// at the same time.
+    pub fn cpu_percent(&self) -> f64 {
+        if self.wall_nanos > 0.0 {
+            (self.user_nanos + self.system_nanos) / self.wall_nanos * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Display for ResourceUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} max RSS, {} user, {} sys, {:.0}% CPU",
+            format_bytes(self.peak_rss_bytes),
+            format_nanos(self.user_nanos),
+            format_nanos(self.system_nanos),
+            self.cpu_percent()
+        )
+    }
+}
+
+/// Format a number of bytes with a binary unit, e.g. `512 B` or `12.0 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Linear interpolation between the closest ranks of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+/// Format a nanosecond value the same way durations are printed elsewhere.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+/// Parse a duration formatted by [`format_nanos`] (e.g. `74.1µs`) back into nanoseconds.
+pub fn parse_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let s = s.trim();
+    let (value, factor) = if let Some(x) = s.strip_suffix("ns") {
+        (x, 1_f64)
+    } else if let Some(x) = s.strip_suffix("µs") {
+        (x, 1_000_f64)
+    } else if let Some(x) = s.strip_suffix("ms") {
+        (x, 1_000_000_f64)
+    } else {
+        (s.strip_suffix('s')?, 1_000_000_000_f64)
+    };
+    value.parse::<f64>().ok().map(|x| x * factor)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            min: get("min")?,
+            median: get("median")?,
+            mean: get("mean")?,
+            std_dev: get("std_dev")?,
+            p95: get("p95")?,
+            // stats stored by older versions do not contain the number of samples.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            samples: get("samples").map(|x| x as u128).unwrap_or_default(),
+        })
+    }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(value.allocations as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            total_bytes: get("total_bytes")?,
+            peak_bytes: get("peak_bytes")?,
+            allocations: get("allocations")?,
+        })
+    }
+}
+
+impl From<&ResourceUsage> for JsonValue {
+    fn from(value: &ResourceUsage) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "peak_rss_bytes".into(),
+            JsonValue::Number(value.peak_rss_bytes as f64),
+        );
+        map.insert("user_nanos".into(), JsonValue::Number(value.user_nanos));
+        map.insert("system_nanos".into(), JsonValue::Number(value.system_nanos));
+        map.insert("wall_nanos".into(), JsonValue::Number(value.wall_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for ResourceUsage {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected usage to be a JSON object.")?;
+
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected usage.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(ResourceUsage {
+            peak_rss_bytes: get("peak_rss_bytes")? as u64,
+            user_nanos: get("user_nanos")?,
+            system_nanos: get("system_nanos")?,
+            wall_nanos: get("wall_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MemoryStats, ResourceUsage, Stats, format_bytes, format_nanos, parse_nanos};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.min, 42.0);
+        assert_eq!(stats.median, 42.0);
+        assert_eq!(stats.mean, 42.0);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.p95, 42.0);
+        assert_eq!(stats.samples, 1);
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_samples(&nanos(&[40, 10, 30, 20])).unwrap();
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.median, 25.0);
+        assert_eq!(stats.mean, 25.0);
+        assert!((stats.std_dev - 12.909_944_487).abs() < 1e-6);
+        assert!((stats.p95 - 38.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parses_formatted_durations() {
+        assert_eq!(parse_nanos("74ns"), Some(74.0));
+        assert_eq!(parse_nanos("74.1µs"), Some(74_100.0));
+        assert_eq!(parse_nanos("2.5ms"), Some(2_500_000.0));
+        assert_eq!(parse_nanos("2.0s"), Some(2_000_000_000.0));
+        assert_eq!(parse_nanos(&format_nanos(1_500.0)), Some(1_500.0));
+        assert_eq!(parse_nanos("-"), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(12 * 1024), "12.0 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 + 512 * 1024), "3.5 MiB");
+
+        let memory = MemoryStats {
+            total_bytes: 4096,
+            peak_bytes: 2048,
+            allocations: 3,
+        };
+        assert_eq!(memory.to_string(), "3 allocs, 2.0 KiB peak, 4.0 KiB total");
+
+        let usage = ResourceUsage {
+            peak_rss_bytes: 12 * 1024 * 1024,
+            user_nanos: 1_500_000.0,
+            system_nanos: 250_000.0,
+            wall_nanos: 875_000.0,
+        };
+        assert_eq!(
+            usage.to_string(),
+            "12.0 MiB max RSS, 1.5ms user, 250.0µs sys, 200% CPU"
+        );
+    }
+
+    #[test]
+    fn is_robust_against_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 10, 10, 10, 1000])).unwrap();
+        assert_eq!(stats.median, 10.0);
+        assert_eq!(stats.mean, 208.0);
+    }
+}
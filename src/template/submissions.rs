@@ -0,0 +1,412 @@
+/// Journal of submitted answers, used to avoid resubmitting answers that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year, data_dir};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// Verdict returned by Advent of Code for a submission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    /// The answer was not checked because the previous submission was too recent.
+    RateLimited,
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+/// Additional information returned for incorrect answers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub verdict: Verdict,
+    pub hint: Option<Hint>,
+}
+
+/// Represents every submission made so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(data_dir(year).join(SUBMISSIONS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty journal.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_dir(year).join(SUBMISSIONS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Record a submission made just now, parsing the verdict from the response text.
+    pub fn push(&mut self, day: Day, part: u8, answer: &str, response: &str) -> &Submission {
+        let (verdict, hint) = parse_verdict(response);
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            verdict,
+            hint,
+        });
+
+        self.data.last().unwrap()
+    }
+
+    /// Check whether an answer is worth submitting, based on previous submissions of the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), String> {
+        let previous = || {
+            self.data
+                .iter()
+                .filter(move |s| s.day == day && s.part == part)
+        };
+
+        if previous().any(|s| s.answer == answer && s.verdict == Verdict::Incorrect) {
+            return Err(format!("`{answer}` was already rejected"));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |hint: Hint| {
+            previous()
+                .filter(move |s| s.hint == Some(hint))
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(too_high) = bound(Hint::TooHigh).min()
+            && value >= too_high
+        {
+            return Err(format!(
+                "`{answer}` is not below `{too_high}`, which was too high"
+            ));
+        }
+
+        if let Some(too_low) = bound(Hint::TooLow).max()
+            && value <= too_low
+        {
+            return Err(format!(
+                "`{answer}` is not above `{too_low}`, which was too low"
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the verdict from the text of a submission response.
+pub fn parse_verdict(response: &str) -> (Verdict, Option<Hint>) {
+    let verdict = if response.contains("That's the right answer") {
+        Verdict::Correct
+    } else if response.contains("That's not the right answer") {
+        Verdict::Incorrect
+    } else if response.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    } else if response.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown
+    };
+
+    let hint = if response.contains("your answer is too high") {
+        Some(Hint::TooHigh)
+    } else if response.contains("your answer is too low") {
+        Some(Hint::TooLow)
+    } else {
+        None
+    };
+
+    (verdict, hint)
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            "wrong_level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            s => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+impl Display for Hint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Hint::TooHigh => "too_high",
+            Hint::TooLow => "too_low",
+        })
+    }
+}
+
+impl FromStr for Hint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "too_high" => Ok(Hint::TooHigh),
+            "too_low" => Ok(Hint::TooLow),
+            s => Err(format!("unknown hint `{s}`.")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        map.insert(
+            "hint".into(),
+            match value.hint {
+                Some(x) => JsonValue::String(x.to_string()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|x| x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|x| x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        let hint = match json.get("hint") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected submission.hint to be null or string.")?
+                    .parse()?,
+            ),
+            _ => None,
+        };
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            timestamp,
+            verdict,
+            hint,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Hint, Submissions, Verdict, parse_verdict};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    const TOO_HIGH: &str = "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data.";
+    const TOO_LOW: &str = "That's not the right answer; your answer is too low.";
+    const WRONG: &str =
+        "That's not the right answer. If you're stuck, make sure you're using the full input data.";
+    const CORRECT: &str =
+        "That's the right answer! You are one gold star closer to decorating the North Pole.";
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(parse_verdict(CORRECT), (Verdict::Correct, None));
+        assert_eq!(parse_verdict(WRONG), (Verdict::Incorrect, None));
+        assert_eq!(
+            parse_verdict(TOO_HIGH),
+            (Verdict::Incorrect, Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            parse_verdict(TOO_LOW),
+            (Verdict::Incorrect, Some(Hint::TooLow))
+        );
+        assert_eq!(
+            parse_verdict(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again."
+            ),
+            (Verdict::RateLimited, None)
+        );
+        assert_eq!(parse_verdict("<html></html>"), (Verdict::Unknown, None));
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "abc", WRONG);
+
+        assert!(submissions.check(day!(1), 1, "abc").is_err());
+        assert!(submissions.check(day!(1), 1, "abd").is_ok());
+        assert!(submissions.check(day!(1), 2, "abc").is_ok());
+        assert!(submissions.check(day!(2), 1, "abc").is_ok());
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "100", TOO_HIGH);
+        submissions.push(day!(1), 1, "200", TOO_HIGH);
+        submissions.push(day!(1), 1, "10", TOO_LOW);
+
+        assert!(submissions.check(day!(1), 1, "100").is_err());
+        assert!(submissions.check(day!(1), 1, "150").is_err());
+        assert!(submissions.check(day!(1), 1, "10").is_err());
+        assert!(submissions.check(day!(1), 1, "5").is_err());
+        assert!(submissions.check(day!(1), 1, "50").is_ok());
+        assert!(submissions.check(day!(1), 2, "150").is_ok());
+    }
+
+    #[test]
+    fn allows_resubmitting_after_rate_limit() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "42", "You gave an answer too recently");
+
+        assert!(submissions.check(day!(1), 1, "42").is_ok());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "100", TOO_HIGH);
+        submissions.push(day!(1), 1, "42", CORRECT);
+
+        let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
@@ -1,37 +1,215 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::calendar;
+use crate::template::protocol::{PARSE_PART, PartResult, PartStatus};
+use crate::template::stats::{MemoryStats, ResourceUsage, Stats, format_nanos, parse_nanos};
+use crate::template::{Day, Year, data_dir};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the JSON document written by [`Timings::store_file`].
+/// Version 1 files (without a `version` key) have no history.
+const SCHEMA_VERSION: f64 = 2.0;
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Name of the input that was timed, `None` for the default input (e.g. `inputs/01.txt`).
+    pub input: Option<String>,
+    /// Time of the parse step, for solutions that parse their input once.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    pub parse_stats: Option<Stats>,
+    /// Heap usage, measured by `cargo time --memory`.
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
+    pub parse_memory: Option<MemoryStats>,
+    /// Peak RSS and CPU time of the whole process, measured by `--resources`.
+    pub usage: Option<ResourceUsage>,
+    /// Parts that returned an error.
+    pub failed: Vec<u8>,
+}
+
+impl Timing {
+    /// A timing of a day without any measurements.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            input: None,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            parse_memory: None,
+            usage: None,
+            failed: vec![],
+        }
+    }
+
+    /// Build a timing from the records reported by a solution binary.
+    pub fn from_results(day: Day, input: Option<&str>, results: &[PartResult]) -> Self {
+        let mut timing = Timing {
+            input: input.map(Into::into),
+            failed: results
+                .iter()
+                .filter(|r| r.status == PartStatus::Failed)
+                .map(|r| r.part)
+                .collect(),
+            ..Timing::new(day)
+        };
+
+        for result in results.iter().filter(|r| r.status == PartStatus::Solved) {
+            let formatted = Some(format_nanos(result.nanos));
+
+            match result.part {
+                PARSE_PART => {
+                    (timing.parse, timing.parse_stats, timing.parse_memory) =
+                        (formatted, result.stats, result.memory);
+                }
+                1 => {
+                    (timing.part_1, timing.part_1_stats, timing.part_1_memory) =
+                        (formatted, result.stats, result.memory);
+                }
+                2 => {
+                    (timing.part_2, timing.part_2_stats, timing.part_2_memory) =
+                        (formatted, result.stats, result.memory);
+                }
+                _ => continue,
+            }
+
+            timing.total_nanos += result.nanos;
+        }
+
+        timing
+    }
+
+    /// Representative runtime of a part (or of the parse step) in nanoseconds: the median if
+    /// statistics are present, otherwise the stored mean.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        let (formatted, stats) = match part {
+            PARSE_PART => (&self.parse, self.parse_stats),
+            1 => (&self.part_1, self.part_1_stats),
+            2 => (&self.part_2, self.part_2_stats),
+            _ => return None,
+        };
+
+        stats
+            .map(|s| s.median)
+            .or_else(|| formatted.as_deref().and_then(parse_nanos))
+    }
+
+    /// Heap usage of a part (or of the parse step), if it was measured.
+    pub fn part_memory(&self, part: u8) -> Option<MemoryStats> {
+        match part {
+            PARSE_PART => self.parse_memory,
+            1 => self.part_1_memory,
+            2 => self.part_2_memory,
+            _ => None,
+        }
+    }
+
+    /// Whether the heap usage of any part was measured.
+    pub fn has_memory(&self) -> bool {
+        [PARSE_PART, 1, 2]
+            .into_iter()
+            .any(|part| self.part_memory(part).is_some())
+    }
+}
+
+/// Describes the circumstances under which a timing was measured.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunInfo {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the git commit, suffixed with `-dirty` if there were uncommitted changes.
+    pub commit: Option<String>,
+    pub profile: String,
+}
+
+impl RunInfo {
+    /// Collect info about the current run.
+    pub fn collect(profile: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        let commit = git(&["rev-parse", "--short", "HEAD"]).map(|hash| {
+            match git(&["status", "--porcelain", "--untracked-files=no"]) {
+                Some(changes) if !changes.is_empty() => format!("{hash}-dirty"),
+                _ => hash,
+            }
+        });
+
+        Self {
+            timestamp,
+            commit,
+            profile: profile.into(),
+        }
+    }
+
+    /// Format the timestamp as an UTC date (`YYYY-MM-DD`).
+    pub fn date(&self) -> String {
+        calendar::format_date(i64::try_from(self.timestamp).unwrap_or(i64::MAX))
+    }
+}
+
+/// A past measurement of a day.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub timing: Timing,
+    pub info: RunInfo,
 }
 
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// Latest timing of every day.
     pub data: Vec<Timing>,
+    /// Every stored measurement, oldest first.
+    pub history: Vec<HistoryEntry>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(data_dir(year).join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_dir(year).join(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -46,13 +224,57 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data
+                .iter()
+                .any(|t| t.day == timing.day && t.input == timing.input)
+            {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by(|a, b| (a.day, &a.input).cmp(&(b.day, &b.input)));
+
+        let mut history = self.history.clone();
+        history.extend(new.history.iter().cloned());
+
+        Timings { data, history }
+    }
+
+    /// Append timings to the history, annotated with info about the run that produced them.
+    pub fn push_history(&mut self, timings: &Self, info: &RunInfo) {
+        self.history
+            .extend(timings.data.iter().map(|timing| HistoryEntry {
+                timing: timing.clone(),
+                info: info.clone(),
+            }));
+    }
+
+    /// All stored measurements of a day, oldest first.
+    pub fn history_for(&self, day: Day) -> Vec<&HistoryEntry> {
+        self.history
+            .iter()
+            .filter(|e| e.timing.day == day)
+            .collect()
+    }
+
+    /// One timing per day: the default input if it was timed, otherwise the first named input.
+    pub fn primary(&self) -> Self {
+        let mut data: Vec<Timing> = vec![];
+
+        for timing in &self.data {
+            match data.iter_mut().find(|t| t.day == timing.day) {
+                Some(existing) if existing.input.is_some() && timing.input.is_none() => {
+                    *existing = timing.clone();
+                }
+                Some(_) => {}
+                None => data.push(timing.clone()),
+            }
+        }
+
+        Timings {
+            data,
+            history: self.history.clone(),
+        }
     }
 
     /// Sum up total duration of timings as millis.
@@ -73,10 +295,15 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(SCHEMA_VERSION));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
 
         JsonValue::Object(map)
     }
@@ -88,19 +315,43 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let document = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = document
+            .get("version")
+            .map(|v| v.get::<f64>().copied())
+            .unwrap_or(Some(1.0))
+            .ok_or("expected `json.version` to be a number.")?;
+
+        if version > SCHEMA_VERSION {
+            return Err(format!("unsupported timings version {version}."));
+        }
+
+        let json_data = document
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let json_history = match document.get("history") {
+            Some(history) => history
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .as_slice(),
+            None => &[],
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -114,6 +365,10 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        if let Some(input) = &value.input {
+            map.insert("input".into(), JsonValue::String(input.clone()));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +388,49 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
+        if let Some(stats) = &value.parse_stats {
+            map.insert("parse_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_1_stats {
+            map.insert("part_1_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_2_stats {
+            map.insert("part_2_stats".into(), JsonValue::from(stats));
+        }
+
+        for (key, memory) in [
+            ("parse_memory", &value.parse_memory),
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            if let Some(memory) = memory {
+                map.insert(key.into(), JsonValue::from(memory));
+            }
+        }
+
+        if let Some(usage) = &value.usage {
+            map.insert("usage".into(), JsonValue::from(usage));
+        }
+
+        if !value.failed.is_empty() {
+            map.insert(
+                "failed".into(),
+                JsonValue::Array(
+                    value
+                        .failed
+                        .iter()
+                        .map(|part| JsonValue::Number(f64::from(*part)))
+                        .collect(),
+                ),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,6 +449,16 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // the input is optional, timings of the default input and of older versions do not name it.
+        let input = match json.get("input") {
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.input to be a string.")?
+                    .clone(),
+            ),
+            None => None,
+        };
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -166,11 +474,120 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // the parse step is only timed for solutions that parse their input once.
+        let parse = match json.get("parse") {
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be a string.")?
+                    .clone(),
+            ),
+            None => None,
+        };
+
+        // statistics are optional, timings stored by older versions do not contain them.
+        let parse_stats = json.get("parse_stats").map(Stats::try_from).transpose()?;
+        let part_1_stats = json.get("part_1_stats").map(Stats::try_from).transpose()?;
+        let part_2_stats = json.get("part_2_stats").map(Stats::try_from).transpose()?;
+
+        // heap usage is only stored by `cargo time --memory`.
+        let memory = |key: &str| json.get(key).map(MemoryStats::try_from).transpose();
+        let usage = json.get("usage").map(ResourceUsage::try_from).transpose()?;
+
+        // parts that returned an error, only stored if there are any.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let failed = match json.get("failed") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.failed to be an array.")?
+                .iter()
+                .map(|part| part.get::<f64>().map(|x| *x as u8))
+                .collect::<Option<Vec<u8>>>()
+                .ok_or("Expected timing.failed to contain part numbers.")?,
+            None => vec![],
+        };
+
         Ok(Timing {
             day,
+            input,
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_stats,
+            part_2_stats,
+            parse_stats,
+            part_1_memory: memory("part_1_memory")?,
+            part_2_memory: memory("part_2_memory")?,
+            parse_memory: memory("parse_memory")?,
+            usage,
+            failed,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut json = JsonValue::from(&value.timing);
+
+        if let Some(map) = json.get_mut::<HashMap<String, JsonValue>>() {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(
+                "timestamp".into(),
+                JsonValue::Number(value.info.timestamp as f64),
+            );
+            map.insert(
+                "commit".into(),
+                match &value.info.commit {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+            map.insert(
+                "profile".into(),
+                JsonValue::String(value.info.profile.clone()),
+            );
+        }
+
+        json
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let timing = Timing::try_from(value)?;
+
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|x| x as u64)
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected history.commit to be null or string.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected history.profile to be a string.")?;
+
+        Ok(HistoryEntry {
+            timing,
+            info: RunInfo {
+                timestamp,
+                commit: commit.cloned(),
+                profile: profile.clone(),
+            },
         })
     }
 }
@@ -187,24 +604,24 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -223,11 +640,28 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "min": 900000, "median": 950000, "mean": 1000000, "std_dev": 5000, "p95": 1200000 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_stats.unwrap().median, 950_000_f64);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 0);
+            assert_eq!(timings.history.len(), 0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_newer_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
         }
 
         #[test]
@@ -247,6 +681,8 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::stats::ResourceUsage;
+        use crate::template::timings::{RunInfo, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,6 +702,145 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_history() {
+            let mut timings = get_mock_timings();
+            let fresh = get_mock_timings();
+            let info = RunInfo {
+                timestamp: 1_733_011_200,
+                commit: Some("abc1234".into()),
+                profile: "release".into(),
+            };
+            timings.push_history(&fresh, &info);
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.history.len(), 3);
+            assert_eq!(parsed.history[0].info, info);
+            assert_eq!(parsed.history[0].timing.part_1, Some("10ms".into()));
+        }
+
+        #[test]
+        fn roundtrips_usage() {
+            let mut timings = get_mock_timings();
+            let usage = ResourceUsage {
+                peak_rss_bytes: 8 * 1024 * 1024,
+                user_nanos: 30_000_000.0,
+                system_nanos: 2_000_000.0,
+                wall_nanos: 16_000_000.0,
+            };
+            timings.data[0].usage = Some(usage);
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.data[0].usage, Some(usage));
+            assert_eq!(parsed.data[1].usage, None);
+        }
+    }
+
+    mod inputs {
+        use crate::template::timings::{Timing, Timings};
+        use tinyjson::JsonValue;
+
+        fn timing(day: u8, input: Option<&str>, total_nanos: f64) -> Timing {
+            Timing {
+                input: input.map(Into::into),
+                part_1: Some("1ms".into()),
+                total_nanos,
+                ..Timing::new(crate::template::Day::new(day).unwrap())
+            }
+        }
+
+        #[test]
+        fn roundtrips_inputs() {
+            let timings = Timings {
+                data: vec![timing(1, None, 1.0), timing(1, Some("alice"), 2.0)],
+                history: vec![],
+            };
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.data[0].input, None);
+            assert_eq!(parsed.data[1].input, Some("alice".into()));
+        }
+
+        #[test]
+        fn merges_inputs_separately() {
+            let stored = Timings {
+                data: vec![timing(1, None, 1.0), timing(1, Some("bob"), 2.0)],
+                history: vec![],
+            };
+            let fresh = Timings {
+                data: vec![timing(1, Some("bob"), 3.0), timing(1, Some("alice"), 4.0)],
+                history: vec![],
+            };
+
+            let merged = stored.merge(&fresh);
+            let inputs: Vec<_> = merged.data.iter().map(|t| t.input.as_deref()).collect();
+            assert_eq!(inputs, vec![None, Some("alice"), Some("bob")]);
+            assert_eq!(merged.data[2].total_nanos, 3.0);
+        }
+
+        #[test]
+        fn prefers_default_input() {
+            let timings = Timings {
+                data: vec![
+                    timing(1, Some("alice"), 1.0),
+                    timing(1, None, 2.0),
+                    timing(2, Some("alice"), 3.0),
+                    timing(2, Some("bob"), 4.0),
+                ],
+                history: vec![],
+            };
+
+            let primary = timings.primary();
+            assert_eq!(primary.data.len(), 2);
+            assert_eq!(primary.data[0].total_nanos, 2.0);
+            assert_eq!(primary.data[1].input, Some("alice".into()));
+        }
+    }
+
+    mod history {
+        use crate::{
+            day,
+            template::timings::{RunInfo, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        fn info(timestamp: u64) -> RunInfo {
+            RunInfo {
+                timestamp,
+                commit: None,
+                profile: "release".into(),
+            }
+        }
+
+        #[test]
+        fn formats_dates() {
+            assert_eq!(info(0).date(), "1970-01-01");
+            assert_eq!(info(951_782_400).date(), "2000-02-29");
+            assert_eq!(info(1_764_547_200).date(), "2025-12-01");
+        }
+
+        #[test]
+        fn keeps_history_when_merging() {
+            let mut stored = Timings::default();
+            stored.push_history(&get_mock_timings(), &info(1));
+
+            let mut merged = stored.merge(&get_mock_timings());
+            merged.push_history(&get_mock_timings(), &info(2));
+
+            let entries = merged.history_for(day!(1));
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].info.timestamp, 1);
+            assert_eq!(entries[1].info.timestamp, 2);
+        }
     }
 
     mod is_day_complete {
@@ -278,42 +853,128 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
+                history: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod from_results {
+        use crate::{
+            day,
+            template::{
+                protocol::{PARSE_PART, PartResult, PartStatus},
+                stats::MemoryStats,
+                timings::Timing,
+            },
+        };
+        use tinyjson::JsonValue;
+
+        fn result(part: u8, status: PartStatus, nanos: f64) -> PartResult {
+            PartResult {
+                part,
+                status,
+                answer: None,
+                error: None,
+                nanos,
+                samples: 10,
+                stats: None,
+                memory: None,
+            }
+        }
+
+        #[test]
+        fn handles_solved_parts() {
+            let timing = Timing::from_results(
+                day!(1),
+                None,
+                &[
+                    result(1, PartStatus::Solved, 74_130.0),
+                    result(2, PartStatus::Solved, 74_130_000.0),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 74_204_130_f64);
+            assert_eq!(timing.part_1.unwrap(), "74.1µs");
+            assert_eq!(timing.part_2.unwrap(), "74.1ms");
+        }
+
+        #[test]
+        fn handles_parse_step() {
+            let timing = Timing::from_results(
+                day!(1),
+                None,
+                &[
+                    result(PARSE_PART, PartStatus::Solved, 1_000.0),
+                    result(1, PartStatus::Solved, 2_000.0),
+                ],
+            );
+            assert_eq!(timing.parse.as_deref(), Some("1.0µs"));
+            assert_eq!(timing.part_nanos(PARSE_PART), Some(1_000.0));
+            assert_eq!(timing.total_nanos, 3_000_f64);
+        }
+
+        #[test]
+        fn handles_memory() {
+            let memory = MemoryStats {
+                total_bytes: 4096,
+                peak_bytes: 1024,
+                allocations: 3,
             };
+            let mut part = result(2, PartStatus::Solved, 1_000.0);
+            part.memory = Some(memory);
+
+            let timing = Timing::from_results(day!(1), None, &[part]);
+            assert_eq!(timing.part_memory(2), Some(memory));
+            assert!(timing.has_memory());
+
+            let parsed = Timing::try_from(&JsonValue::from(&timing)).unwrap();
+            assert_eq!(parsed.part_2_memory, Some(memory));
+            assert_eq!(parsed.part_1_memory, None);
+        }
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+        #[test]
+        fn handles_unsolved_parts() {
+            let timing = Timing::from_results(
+                day!(1),
+                None,
+                &[
+                    result(1, PartStatus::Solved, 2_000_000_000.0),
+                    result(2, PartStatus::Unsolved, 100.0),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 2_000_000_000_f64);
+            assert_eq!(timing.part_1.unwrap(), "2.0s");
+            assert!(timing.part_2.is_none());
         }
     }
 
@@ -329,12 +990,8 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +1006,8 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
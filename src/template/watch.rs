@@ -0,0 +1,146 @@
+/// Detects changes to the files of a day and compares the answers of consecutive runs.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::template::protocol::PartResult;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Year, data_dir};
+
+/// Modification times of the watched files. Files that are added or removed change the snapshot.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Files that affect the result of a day: its binary, `src/lib.rs` and its data files,
+/// e.g. `data/2025/inputs/01.txt`, `data/2025/inputs/01/alice.txt` or `data/2025/examples/01-2.txt`.
+pub fn watched_paths(year: Year, day: Day) -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(format!("src/bin/{year}-{day}.rs")),
+        PathBuf::from("src/lib.rs"),
+    ];
+
+    let prefix = day.to_string();
+
+    for folder in read_dir(&data_dir(year)).filter(|path| path.is_dir()) {
+        paths.extend(read_dir(&folder).filter(|path| {
+            path.extension().is_some_and(|ext| ext == "txt")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&prefix))
+        }));
+
+        // named inputs, e.g. `data/2025/inputs/01/alice.txt`.
+        paths.extend(
+            read_dir(&folder.join(&prefix))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt")),
+        );
+    }
+
+    paths.sort();
+    paths
+}
+
+/// Take a snapshot of the watched files of a day.
+pub fn snapshot(year: Year, day: Day) -> Snapshot {
+    watched_paths(year, day)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Describe how the answers of a run changed compared to the previous run.
+pub fn answer_changes(previous: &[PartResult], current: &[PartResult]) -> Vec<String> {
+    let answer = |results: &[PartResult], part: u8| {
+        results
+            .iter()
+            .find(|r| r.part == part)
+            .and_then(|r| r.answer.clone())
+    };
+
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let before = answer(previous, part);
+            let after = answer(current, part);
+
+            match (before, after) {
+                (before, after) if before == after => None,
+                (None, Some(after)) => {
+                    Some(format!("Part {part}: {ANSI_BOLD}{after}{ANSI_RESET} (new)"))
+                }
+                (Some(before), None) => Some(format!("Part {part}: {before} → ✖")),
+                (Some(before), Some(after)) => Some(format!(
+                    "Part {part}: {before} → {ANSI_BOLD}{after}{ANSI_RESET}"
+                )),
+                (None, None) => None,
+            }
+        })
+        .collect()
+}
+
+fn read_dir(path: &Path) -> impl Iterator<Item = PathBuf> {
+    fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_changes, watched_paths};
+    use crate::template::protocol::{PartResult, PartStatus};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+    use crate::{day, year};
+    use std::path::PathBuf;
+
+    fn result(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            part,
+            status: match answer {
+                Some(_) => PartStatus::Solved,
+                None => PartStatus::Unsolved,
+            },
+            answer: answer.map(Into::into),
+            error: None,
+            nanos: 0.0,
+            samples: 1,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn watches_bin_lib_and_data() {
+        let paths = watched_paths(year!(2025), day!(1));
+        assert!(paths.contains(&PathBuf::from("src/bin/2025-01.rs")));
+        assert!(paths.contains(&PathBuf::from("src/lib.rs")));
+        assert!(paths.contains(&PathBuf::from("data/2025/examples/01.txt")));
+        assert!(!paths.iter().any(|p| p.ends_with("02.txt")));
+    }
+
+    #[test]
+    fn reports_changed_answers() {
+        let previous = vec![result(1, Some("11")), result(2, None)];
+        let current = vec![result(1, Some("12")), result(2, Some("31"))];
+
+        assert_eq!(
+            answer_changes(&previous, &current),
+            vec![
+                format!("Part 1: 11 → {ANSI_BOLD}12{ANSI_RESET}"),
+                format!("Part 2: {ANSI_BOLD}31{ANSI_RESET} (new)"),
+            ]
+        );
+        assert!(answer_changes(&current, &current).is_empty());
+        assert_eq!(
+            answer_changes(&current, &[result(1, Some("12"))]),
+            vec!["Part 2: 31 → ✖"]
+        );
+    }
+}
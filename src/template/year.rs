@@ -0,0 +1,180 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The first year Advent of Code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// A year of advent (i.e. an integer starting from 2015).
+///
+/// Inputs, puzzles and solutions of a year are stored side by side with those of other years,
+/// e.g. `data/2025/inputs/01.txt` and `src/bin/2025-01.rs`.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2025).unwrap();
+/// assert_eq!(year.to_string(), "2025")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The default year, read from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Finds the year in the name of a solution, e.g. `2025_01` or `solutions::day_2025_01`.
+    /// Used by `solution!` to derive the year of a binary at compile time.
+    pub const fn from_name(name: &str) -> Option<Self> {
+        let bytes = name.as_bytes();
+        let mut i = 0;
+
+        while i + 4 <= bytes.len() {
+            let mut year: u16 = 0;
+            let mut j = 0;
+
+            while j < 4 && bytes[i + j].is_ascii_digit() {
+                year = year * 10 + (bytes[i + j] - b'0') as u16;
+                j += 1;
+            }
+
+            let is_delimited = i + 4 == bytes.len() || !bytes[i + 4].is_ascii_digit();
+
+            if j == 4 && is_delimited {
+                return Self::new(year);
+            }
+
+            i += j + 1;
+        }
+
+        None
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.trim().parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year starting from {FIRST_YEAR}")
+    }
+}
+
+/// Parses a single year (`2025`), a list of years (`2023,2025`) or an inclusive range (`2023-2025` or
+/// `2023..=2025`).
+/// The returned years are sorted and free of duplicates.
+pub fn parse_years(s: &str) -> Result<Vec<Year>, YearFromStrError> {
+    let mut years = vec![];
+
+    for item in s.split(',') {
+        match item.split_once("..=").or_else(|| item.split_once('-')) {
+            Some((start, end)) => {
+                let start: Year = start.parse()?;
+                let end: Year = end.parse()?;
+                if start > end {
+                    return Err(YearFromStrError);
+                }
+                years.extend((start.0..=end.0).map(Year));
+            }
+            None => years.push(item.parse()?),
+        }
+    }
+
+    years.sort_unstable();
+    years.dedup();
+    Ok(years)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {
+        const {
+            $crate::template::Year::new($year)
+                .expect("invalid year, expecting a value starting from 2015")
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Year, parse_years};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2025".parse::<Year>().unwrap(), Year(2025));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("foo".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn parses_year_lists_and_ranges() {
+        assert_eq!(parse_years("2025").unwrap(), vec![Year(2025)]);
+        assert_eq!(
+            parse_years("2025,2023").unwrap(),
+            vec![Year(2023), Year(2025)]
+        );
+        assert_eq!(
+            parse_years("2022-2024").unwrap(),
+            vec![Year(2022), Year(2023), Year(2024)]
+        );
+        assert_eq!(
+            parse_years("2023..=2024,2024").unwrap(),
+            vec![Year(2023), Year(2024)]
+        );
+        assert!(parse_years("2010-2012").is_err());
+        assert!(parse_years("2024-2022").is_err());
+    }
+
+    #[test]
+    fn finds_year_in_names() {
+        assert_eq!(Year::from_name("2025_01"), Some(Year(2025)));
+        assert_eq!(
+            Year::from_name("advent_of_code::solutions::day_2024_07"),
+            Some(Year(2024))
+        );
+        assert_eq!(Year::from_name("01"), None);
+        assert_eq!(Year::from_name("advent_of_code::day_01"), None);
+        assert_eq!(Year::from_name("12345"), None);
+    }
+}